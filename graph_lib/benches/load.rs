@@ -0,0 +1,67 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use graph_lib::graph::Graph;
+use graph_lib::handler::GraphHandler;
+use graph_lib::node::Node;
+
+// Benchmarks of loading graphs of different sizes
+// Time per node must stay the same while the number of nodes grows
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+// Function builds a TGF string of a graph with 'size' nodes and about two edges per node
+fn tgf_text(size: usize) -> String {
+    let mut text = String::from("0 Root 0\n");
+    for index in 1..size {
+        text.push_str(&format!("{} {}\n", index, index));
+    }
+    text.push_str("#\n");
+    for index in 1..size {
+        text.push_str(&format!("{} {}\n", index - 1, index));
+        if index / 2 != index - 1 {
+            text.push_str(&format!("{} {}\n", index / 2, index));
+        }
+    }
+    text
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize");
+    group.sample_size(10);
+    let handler = GraphHandler::new();
+    for size in SIZES {
+        let text = tgf_text(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &text, |b, text| {
+            b.iter(|| {
+                let mut graph: Graph<usize> = Graph::new();
+                handler.from_tgf_str(&mut graph, text).unwrap();
+                graph
+            })
+        });
+    }
+    group.finish();
+}
+
+fn build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build");
+    group.sample_size(10);
+    for size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter(|| {
+                let mut graph = Graph::new();
+                for index in 0..size {
+                    graph.add_node(Node::new(index, index, None)).unwrap();
+                }
+                for index in 1..size {
+                    graph.add_edge(index - 1, index).unwrap();
+                }
+                graph
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, deserialize, build);
+criterion_main!(benches);
@@ -0,0 +1,126 @@
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, SamplingMode, Throughput};
+use criterion::measurement::WallTime;
+use graph_lib::graph::Graph;
+use graph_lib::iterator::GraphIter;
+use graph_lib::node::Node;
+use std::time::Duration;
+
+// Benchmarks of BFS and DFS of GraphIter
+// 'reference' is the previous implementation (Vec queue with remove(0), Vec of visited nodes).
+// It is quadratic, so on the 1M-edge graph it is measured in a separate group with the fewest samples
+
+// Number of edges going out of each node
+const DEGREE: usize = 10;
+// Total numbers of edges in the benchmarked graphs
+const EDGES: [usize; 3] = [10_000, 100_000, 1_000_000];
+// Largest graph the reference implementation is measured on together with GraphIter
+const REFERENCE_LIMIT: usize = 100_000;
+// Time to measure the reference on larger graphs. One run takes seconds, so the time is
+// stretched to fit the 10 samples, which keeps each sample to a single run
+const REFERENCE_TIME: Duration = Duration::from_secs(20);
+
+// Function builds a connected graph with 'edges' edges, DEGREE edges per node
+fn build_graph(edges: usize) -> Graph<()> {
+    let size = edges / DEGREE;
+    let mut graph = Graph::new();
+    for index in 0..size {
+        let connected = (1..=DEGREE).map(|k| (index + k * k * 7 + k) % size).collect();
+        graph.add_node(Node::new(index, (), Some(connected))).unwrap();
+    }
+    graph.set_root(Some(0)).unwrap();
+    graph
+}
+
+// Previous implementation of BFS
+fn reference_bfs(graph: &Graph<()>, root: usize) -> usize {
+    let mut stack = vec![root];
+    let mut visited: Vec<usize> = vec![];
+    while !stack.is_empty() {
+        let node_index = stack.remove(0);
+        if visited.contains(&node_index) {
+            continue;
+        }
+        visited.push(node_index);
+        for next in graph.get_node(node_index).unwrap().connected().iter() {
+            stack.push(*next);
+        }
+    }
+    visited.len()
+}
+
+// Previous implementation of DFS
+fn reference_dfs(graph: &Graph<()>, root: usize) -> usize {
+    let mut stack = vec![root];
+    let mut visited: Vec<usize> = vec![];
+    while let Some(node_index) = stack.pop() {
+        if visited.contains(&node_index) {
+            continue;
+        }
+        visited.push(node_index);
+        let mut clone = graph.get_node(node_index).unwrap().connected().clone();
+        clone.reverse();
+        stack.extend(clone);
+    }
+    visited.len()
+}
+
+fn traversal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traversal");
+    group.sample_size(10);
+    for edges in EDGES {
+        let graph = build_graph(edges);
+        group.throughput(Throughput::Elements(edges as u64));
+        group.bench_with_input(BenchmarkId::new("bfs", edges), &graph, |b, graph| {
+            b.iter(|| {
+                let mut iter = GraphIter::new(graph.root).unwrap();
+                let mut count = 0;
+                while iter.next_breadth_search(graph).is_some() {
+                    count += 1;
+                }
+                count
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("dfs", edges), &graph, |b, graph| {
+            b.iter(|| {
+                let mut iter = GraphIter::new(graph.root).unwrap();
+                let mut count = 0;
+                while iter.next_depth_search(graph).is_some() {
+                    count += 1;
+                }
+                count
+            })
+        });
+        if edges <= REFERENCE_LIMIT {
+            bench_reference(&mut group, &graph, edges);
+        }
+    }
+    group.finish();
+}
+
+fn traversal_reference(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traversal");
+    // Each sample is a single run
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+    group.warm_up_time(Duration::from_secs(1));
+    group.measurement_time(REFERENCE_TIME);
+    for edges in EDGES.into_iter().filter(|&edges| edges > REFERENCE_LIMIT) {
+        let graph = build_graph(edges);
+        group.throughput(Throughput::Elements(edges as u64));
+        bench_reference(&mut group, &graph, edges);
+    }
+    group.finish();
+}
+
+// Function measures the reference BFS and DFS on the graph
+fn bench_reference(group: &mut BenchmarkGroup<'_, WallTime>, graph: &Graph<()>, edges: usize) {
+    group.bench_with_input(BenchmarkId::new("reference_bfs", edges), graph, |b, graph| {
+        b.iter(|| reference_bfs(graph, 0))
+    });
+    group.bench_with_input(BenchmarkId::new("reference_dfs", edges), graph, |b, graph| {
+        b.iter(|| reference_dfs(graph, 0))
+    });
+}
+
+criterion_group!(benches, traversal, traversal_reference);
+criterion_main!(benches);
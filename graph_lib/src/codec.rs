@@ -0,0 +1,56 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Module of a value codec
+
+// Trait describes how a value of a node (or a payload of an edge) is turned into a TGF label.
+// It is all that is needed to write a graph. Any type that implements 'Display' gets it for free.
+// Other types can implement it by hand.
+// M is a marker telling apart implementations that would otherwise overlap.
+// It is inferred by the compiler and never has to be written out.
+pub trait ValueEncode<M = Text> {
+    // Function writes a value as a label. The label must fit into a single line
+    fn encode(&self) -> String;
+}
+
+// Trait describes how a TGF label is turned back into a value. It is needed to read a graph.
+// Any type that implements both 'Display' and 'FromStr' gets it for free.
+pub trait ValueCodec<M = Text>: ValueEncode<M> + Sized {
+    // Function parses a label back into a value
+    fn decode(label: &str) -> Result<Self, String>;
+}
+
+// Marker of the codec of types implementing 'Display' and 'FromStr'
+pub enum Text {}
+
+// Marker of the codec of edges without payloads
+pub enum Unlabeled {}
+
+impl<T: Display + ?Sized> ValueEncode<Text> for T {
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T> ValueCodec<Text> for T
+where
+    T: Display + FromStr,
+    T::Err: Display,
+{
+    fn decode(label: &str) -> Result<Self, String> {
+        label.parse().map_err(|err: T::Err| err.to_string())
+    }
+}
+
+// Edges without payloads have no labels. Labels found in the input are ignored
+impl ValueEncode<Unlabeled> for () {
+    fn encode(&self) -> String {
+        String::new()
+    }
+}
+
+impl ValueCodec<Unlabeled> for () {
+    fn decode(_label: &str) -> Result<Self, String> {
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use super::graph::{Graph, EdgeType, Directed};
+use super::node::Node;
+use std::collections::{HashMap, HashSet};
+
+// Module of components of a graph
+
+impl<T, E, D: EdgeType> Graph<T, E, D> {
+
+    // Function finds connected components of the graph. Direction of edges is ignored
+    // Components are listed in the order of their first nodes in the arena. Indexes of nodes of each component are sorted
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut component_of: HashMap<usize, usize> = HashMap::with_capacity(self.len());
+        let mut components = Vec::new();
+        for start in self.nodes().map(|node| node.index) {
+            if component_of.contains_key(&start) {
+                continue;
+            }
+            let id = components.len();
+            component_of.insert(start, id);
+            let mut members = vec![start];
+            // Members are also the queue of the breadth-first-search
+            let mut next = 0;
+            while let Some(&node) = members.get(next) {
+                next += 1;
+                let sources = self.predecessors(node).unwrap_or(&[]);
+                for other in self.neighbors(node).chain(sources.iter().copied()) {
+                    if self.in_graph(other) && !component_of.contains_key(&other) {
+                        component_of.insert(other, id);
+                        members.push(other);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members);
+        }
+        components
+    }
+}
+
+impl<T, E> Graph<T, E, Directed> {
+
+    // Function finds strongly connected components of the graph (Tarjan's algorithm)
+    // Each node of a component can be reached from every other node of it
+    // Components are returned in topological order: edges between them only go from earlier to later ones
+    // Indexes of nodes of each component are sorted
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // Order in which nodes are discovered by the depth-first-search
+        let mut order: HashMap<usize, usize> = HashMap::with_capacity(self.len());
+        // Earliest discovered node on the stack reachable from each node
+        let mut low: HashMap<usize, usize> = HashMap::with_capacity(self.len());
+        // Nodes whose components are not found yet
+        let mut stack = Vec::new();
+        let mut on_stack = HashSet::new();
+        let mut components = Vec::new();
+        for start in self.nodes().map(|node| node.index) {
+            if order.contains_key(&start) {
+                continue;
+            }
+            // Path of the depth-first-search with positions of the next edges of its nodes
+            let mut path = vec![(start, 0)];
+            let discovered = order.len();
+            order.insert(start, discovered);
+            low.insert(start, discovered);
+            stack.push(start);
+            on_stack.insert(start);
+            while let Some(&(node, next)) = path.last() {
+                let successors = self.successors(node).unwrap_or(&[]);
+                if let Some(&child) = successors.get(next) {
+                    path.last_mut().unwrap().1 += 1;
+                    match order.get(&child) {
+                        // Edges to nodes that are not in the graph are skipped
+                        None if self.in_graph(child) => {
+                            let discovered = order.len();
+                            order.insert(child, discovered);
+                            low.insert(child, discovered);
+                            stack.push(child);
+                            on_stack.insert(child);
+                            path.push((child, 0));
+                        }
+                        Some(&discovered) if on_stack.contains(&child) => {
+                            let lowest = low[&node].min(discovered);
+                            low.insert(node, lowest);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                // All edges of the node are processed
+                path.pop();
+                if let Some(&(parent, _)) = path.last() {
+                    let lowest = low[&parent].min(low[&node]);
+                    low.insert(parent, lowest);
+                }
+                // Node that can't reach anything discovered earlier closes a component
+                if low[&node] == order[&node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        // Tarjan's algorithm finds components in reverse topological order
+        components.reverse();
+        components
+    }
+
+    // Function builds a graph whose nodes are strongly connected components of this graph
+    // Index of a node is the position of its component in strongly_connected_components(),
+    // its value is the list of indexes of the component's members
+    // There is an edge between two components if any of their members are connected
+    // The result has no cycles, so it can be sorted topologically
+    pub fn condensation(&self) -> Graph<Vec<usize>> {
+        let components = self.strongly_connected_components();
+        let component_of: HashMap<usize, usize> = components.iter()
+            .enumerate()
+            .flat_map(|(i, members)| members.iter().map(move |&member| (member, i)))
+            .collect();
+        let mut graph = Graph::new();
+        for (i, members) in components.into_iter().enumerate() {
+            // Each pair of components is connected once
+            let mut connected: Vec<usize> = Vec::new();
+            let mut seen = HashSet::new();
+            for &member in members.iter() {
+                for to in self.successors(member).unwrap_or(&[]) {
+                    if let Some(&j) = component_of.get(to) {
+                        if j != i && seen.insert(j) {
+                            connected.push(j);
+                        }
+                    }
+                }
+            }
+            graph.add_node(Node::new(i, members, Some(connected))).expect("Components Have Distinct Indexes");
+        }
+        if let Some(root) = self.root {
+            graph.root = component_of.get(&root).copied();
+        }
+        graph
+    }
+}
@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+// Module of graph errors
+
+// Enum of all errors that can occur while working with a graph
+#[derive(Debug)]
+pub enum GraphError {
+    // Node with this index is already in the graph
+    DuplicateNode(usize),
+    // Node with this index is not in the graph
+    MissingNode(usize),
+    // Edge from the node to itself
+    SelfLoop(usize),
+    // Edge between these nodes already exists
+    DuplicateEdge(usize, usize),
+    // Edge between these nodes does not exist
+    MissingEdge(usize, usize),
+    // Edge with this identifier does not exist
+    MissingEdgeId(usize),
+    // All node indexes that insert() can give out are taken
+    NoFreeIndex,
+    // Graph (or iterator) has no root
+    RootNotSet,
+    // Graph has a cycle going through these nodes (in order)
+    Cycle(Vec<usize>),
+    // Graph has a cycle of negative total length going through these nodes (in order)
+    NegativeCycle(Vec<usize>),
+    // Edge between these nodes has a negative length where only non-negative ones are allowed
+    NegativeWeight(usize, usize),
+    // Reading or writing failed
+    Io(io::Error),
+    // TGF input is malformed. Line and column (in bytes) start with 1
+    Parse { line: usize, column: usize, reason: String },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::DuplicateNode(index) => write!(f, "Node {} is Already in The Graph", index),
+            GraphError::MissingNode(index) => write!(f, "Node {} does not Exist in the Graph!", index),
+            GraphError::SelfLoop(index) => write!(f, "Can't Form an Edge From the Node {} to Itself!", index),
+            GraphError::DuplicateEdge(from, to) => write!(f, "Multiple Edges From Node {} to Node {} are Forbidden!", from, to),
+            GraphError::MissingEdge(from, to) => write!(f, "The Edge From Node {} to Node {} Does Not Exist!", from, to),
+            GraphError::MissingEdgeId(id) => write!(f, "The Edge {} Does Not Exist!", id),
+            GraphError::NoFreeIndex => write!(f, "No Free Node Indexes Left!"),
+            GraphError::RootNotSet => write!(f, "The Root of the Graph is not Set!"),
+            GraphError::Cycle(nodes) => write!(f, "The Graph Has a Cycle Through Nodes {:?}", nodes),
+            GraphError::NegativeCycle(nodes) => write!(f, "The Graph Has a Negative Cycle Through Nodes {:?}", nodes),
+            GraphError::NegativeWeight(from, to) => write!(f, "The Edge From Node {} to Node {} Has a Negative Length!", from, to),
+            GraphError::Io(err) => write!(f, "I/O Error: {}", err),
+            GraphError::Parse { line, column, reason } => write!(f, "Parse Error at Line {}, Column {}: {}", line, column, reason),
+        }
+    }
+}
+
+impl Error for GraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GraphError {
+    fn from(err: io::Error) -> Self {
+        GraphError::Io(err)
+    }
+}
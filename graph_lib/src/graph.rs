@@ -1,153 +1,546 @@
-use super::node::Node;
-use super::iterator::GraphIter;
+use super::node::{Node, NodeId, EdgeId};
+use super::iterator::{GraphIter, Order, Traversal, Forest, DepthBfs};
+use super::error::GraphError;
 use std::fmt::{Display, Debug};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
 
 // Module of a graph
 
+// Trait tells directed graphs apart from undirected ones
+pub trait EdgeType {
+    fn is_directed() -> bool;
+}
+
+// Marker of a directed graph. Edge 'a b' goes from 'a' to 'b' only
+pub enum Directed {}
+
+// Marker of an undirected graph. Edge 'a b' is the same edge as 'b a'
+pub enum Undirected {}
+
+impl EdgeType for Directed {
+    fn is_directed() -> bool {
+        true
+    }
+}
+
+impl EdgeType for Undirected {
+    fn is_directed() -> bool {
+        false
+    }
+}
+
+// Undirected graph
+pub type UnGraph<T, E = ()> = Graph<T, E, Undirected>;
+
+// Struct of a policy telling which edges are allowed in the graph
+// By default neither self-loops nor parallel edges are allowed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GraphPolicy {
+    // Edge from the node to itself
+    pub allow_self_loops: bool,
+    // Several edges between the same pair of nodes
+    pub allow_parallel_edges: bool,
+}
+
+impl GraphPolicy {
+    // Policy of a multigraph. Allows both self-loops and parallel edges
+    pub fn multigraph() -> Self {
+        GraphPolicy { allow_self_loops: true, allow_parallel_edges: true }
+    }
+}
+
 // Struct of a graph
-pub struct Graph<T> {
+// T is the type of values of nodes, E is the type of payloads (e.g. weights) of edges
+// D tells if edges are directed or not
+// Each undirected edge is stored once, in the node it was added from
+pub struct Graph<T, E = (), D = Directed> {
     // Graph has a root and an arena
     // Arena is a vector holding nodes of a graph. Allows for random access without nested borrowing
     // Access to each node from arena is through it's index.
-    pub arena: Vec<Node<T>>,
+    // Arena can only be changed by the graph itself, so that it stays consistent with 'slots'
+    pub(crate) arena: Vec<Node<T, E>>,
+    // Map from the index of a node to its position (slot) in the arena
+    slots: HashMap<usize, usize>,
+    // Reverse index of edges. Map from the index of a node to indexes of nodes having edges to it
+    incoming: HashMap<usize, Vec<usize>>,
+    // Map from the identifier of an edge to the index of the node it is stored in
+    edge_sources: HashMap<EdgeId, usize>,
+    // Identifier of the next added edge
+    next_edge: EdgeId,
+    // Index given out to the next inserted node. Greater than any index ever used in the graph
+    // 'None' if the greatest possible index has been used
+    next_node: Option<NodeId>,
+    // Number of nodes removed so far. Removal moves a node to another place in the arena,
+    // so iterators keeping positions in the arena use it to notice that
+    pub(crate) removals: usize,
     // Root is one of the nodes in arena. Access through index as well.
     pub root: Option<usize>,
+    // Edges allowed in the graph
+    policy: GraphPolicy,
+    direction: PhantomData<D>,
 }
 
 impl<T> Graph<T> {
-    // Constructor of a graph
+    // Constructor of a directed graph without edge payloads
     // At first, graph has no root. It must be set with set_root()
+    // Other graphs (with edge payloads or undirected) are created with Graph::default()
     pub fn new() -> Self {
-        Graph{arena: Vec::new(), root: None}
+        Graph::default()
+    }
+}
+
+impl<T, D: EdgeType> Graph<T, (), D> {
+    // Function creates an edge of the graph between two nodes
+    pub fn add_edge(&mut self, from: usize, to: usize) -> Result<EdgeId, GraphError> {
+        self.add_edge_with(from, to, ())
+    }
+}
+
+impl<T, E, D> Default for Graph<T, E, D> {
+    fn default() -> Self {
+        Graph::with_policy(GraphPolicy::default())
+    }
+}
+
+impl<T, E, D> Graph<T, E, D> {
+    // Constructor of a graph with a given policy of edges
+    pub fn with_policy(policy: GraphPolicy) -> Self {
+        Graph {
+            arena: Vec::new(),
+            slots: HashMap::new(),
+            incoming: HashMap::new(),
+            edge_sources: HashMap::new(),
+            next_edge: 0,
+            next_node: Some(0),
+            removals: 0,
+            root: None,
+            policy,
+            direction: PhantomData,
+        }
+    }
+
+    // Getter for 'policy'
+    pub fn policy(&self) -> GraphPolicy {
+        self.policy
+    }
+}
+
+impl<T, E, D: EdgeType> Graph<T, E, D> {
+
+    // Function checks if edges of the graph are directed
+    pub fn is_directed(&self) -> bool {
+        D::is_directed()
+    }
+
+    // Function returns the number of nodes in the graph
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    // Function checks if the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+
+    // Function returns an iterator over all nodes of the graph (in arena order)
+    pub fn nodes(&self) -> impl Iterator<Item = &Node<T, E>> {
+        self.arena.iter()
     }
 
     // Function adds a node to the graph
-    pub fn add_node(&mut self, node: Node<T>) -> Result<(), String>{  
+    pub fn add_node(&mut self, mut node: Node<T, E>) -> Result<(), GraphError> {
 
         // Check if such node is not present in the graph
-        if !self.get_node(node.index).is_some() {
+        if !self.in_graph(node.index) {
+            // Edges the node comes with follow the same policy as edges added later
+            self.check_new_edges(&node)?;
+            // Edges the node comes with get identifiers and are added to the reverse index
+            node.assign_edge_ids(self.next_edge);
+            self.next_edge += node.connected().len();
+            for (id, to, _) in node.edges_with_ids() {
+                self.incoming.entry(to).or_default().push(node.index);
+                self.edge_sources.insert(id, node.index);
+            }
+            // Indexes given out by insert() never collide with explicit ones
+            self.next_node = match (self.next_node, node.index.checked_add(1)) {
+                (Some(next), Some(after)) => Some(next.max(after)),
+                _ => None,
+            };
+            self.slots.insert(node.index, self.arena.len());
             self.arena.push(node);
             Ok(())
         } else {
-            Err(format!("Node {} is Already in The Graph", node.index))
+            Err(GraphError::DuplicateNode(node.index))
         }
     }
 
 
+    // Function checks edges of a node that is about to be added against the policy of the graph
+    fn check_new_edges(&self, node: &Node<T, E>) -> Result<(), GraphError> {
+        let connected = node.connected();
+        for (i, &to) in connected.iter().enumerate() {
+            if to == node.index && !self.policy.allow_self_loops {
+                return Err(GraphError::SelfLoop(to));
+            }
+            if !self.policy.allow_parallel_edges && connected[..i].contains(&to) {
+                return Err(GraphError::DuplicateEdge(node.index, to));
+            }
+            // Undirected edge may already be stored in the other node
+            let stored_in_other = !D::is_directed() && to != node.index
+                && self.get_node(to).is_some_and(|other| other.connected().contains(&node.index));
+            if !self.policy.allow_parallel_edges && stored_in_other {
+                return Err(GraphError::DuplicateEdge(node.index, to));
+            }
+        }
+        Ok(())
+    }
+
+    // Function adds a node with a given value to the graph and returns its new index
+    // Index is never reused, even after the node is removed
+    // Once a node with the largest possible index is added, no index is left to give out
+    pub fn insert(&mut self, value: T) -> Result<NodeId, GraphError> {
+        let index = self.next_node.ok_or(GraphError::NoFreeIndex)?;
+        self.add_node(Node::with_edges(index, value, Vec::new()))?;
+        Ok(index)
+    }
+
     // Function checks if node exists in the graph
     pub fn in_graph(&self, index: usize) -> bool {
-        if self.get_node(index).is_some() {
-            return true;
-        }
-        return false;
+        self.slots.contains_key(&index)
     }
 
-    // Function removes a node with a given index
-    pub fn remove_node(&mut self, index: usize) -> Result<(), String> {
-        if !self.in_graph(index) {
-            return Err(format!("Node {} does not Exist in the Graph!", index))
+    // Function removes a node with a given index and returns it
+    // All edges to and from the node are removed from the graph as well
+    pub fn remove_node(&mut self, index: usize) -> Result<Node<T, E>, GraphError> {
+        let slot = self.slots.remove(&index).ok_or(GraphError::MissingNode(index))?;
+        // The last node of the arena takes the place of the removed one
+        let node = self.arena.swap_remove(slot);
+        self.removals += 1;
+        if let Some(moved) = self.arena.get(slot) {
+            self.slots.insert(moved.index, slot);
         }
-        self.arena.retain(|x| x.index != index);
-        Ok(())
+        // Remove edges coming into the node
+        for from in self.incoming.remove(&index).unwrap_or_default() {
+            if let Some(other) = self.get_node_mut(from) {
+                for id in other.remove_edges_to(index) {
+                    self.edge_sources.remove(&id);
+                }
+            }
+        }
+        // Remove edges going out of the node from the reverse index
+        for (id, to, _) in node.edges_with_ids() {
+            self.unlink_incoming(index, to);
+            self.edge_sources.remove(&id);
+        }
+        // Graph without the root node has no root
+        if self.root == Some(index) {
+            self.root = None;
+        }
+        Ok(node)
     }
 
     // Function gets the node from the graph (borrows it)
-    pub fn get_node(&self, index: usize) -> Option<&Node<T>> {
-        for node in self.arena.iter() {
-            if node.index == index {
-                return Some(node)
-            }
-        }
-        None
+    pub fn get_node(&self, index: usize) -> Option<&Node<T, E>> {
+        self.slots.get(&index).map(|&slot| &self.arena[slot])
     }
 
     // Function gets a mutable node from the graph (mutably borrows it)
-    pub fn get_node_mut(&mut self, index: usize) -> Option<&mut Node<T>> {
-        for node in self.arena.iter_mut() {
-            if node.index == index {
-                return Some(node)
-            }
+    // Index of the node must not be changed through it
+    pub fn get_node_mut(&mut self, index: usize) -> Option<&mut Node<T, E>> {
+        self.slots.get(&index).map(|&slot| &mut self.arena[slot])
+    }
+
+
+    // Next functions follow edges in the direction they were added in (for undirected graphs as well)
+    // Function 'neighbors()' follows undirected edges both ways
+
+    // Function returns indexes of nodes having edges to the given node (one entry per edge)
+    pub fn predecessors(&self, index: usize) -> Option<&[usize]> {
+        if !self.in_graph(index) {
+            return None
         }
-        None
+        Some(self.incoming.get(&index).map_or(&[], |sources| sources.as_slice()))
+    }
+
+    // Function returns indexes of nodes the given node has edges to
+    pub fn successors(&self, index: usize) -> Option<&[usize]> {
+        self.get_node(index).map(|node| node.connected().as_slice())
+    }
+
+    // Function returns the number of edges coming into the node
+    pub fn in_degree(&self, index: usize) -> Option<usize> {
+        self.predecessors(index).map(|sources| sources.len())
+    }
+
+    // Function returns the number of edges going out of the node
+    pub fn out_degree(&self, index: usize) -> Option<usize> {
+        self.successors(index).map(|targets| targets.len())
+    }
+
+    // Function returns edges coming into the node as (from, to) pairs
+    pub fn incoming_edges(&self, index: usize) -> Option<impl Iterator<Item = (usize, usize)> + '_> {
+        self.predecessors(index).map(|sources| sources.iter().map(move |&from| (from, index)))
     }
 
 
     // Function makes a node with a given index a root of a graph
-    pub fn set_root(&mut self, root: Option<usize>) -> Result<(), String> {
-        // Once root has been set to 'Some' it can't be set to 'None'
-        if self.root.is_some() && root.is_none() {
-            return Err(String::from("Can't Set the Root to 'None' if It Has Already Been Set to 'Some'"))
-        }
+    pub fn set_root(&mut self, root: Option<usize>) -> Result<(), GraphError> {
+        // Root can't be set to 'None'
+        let root = root.ok_or(GraphError::RootNotSet)?;
         // Check if a given root exists in graph
-        if root.is_some() && self.in_graph(root.unwrap()) {
-            self.root = root;
+        if self.in_graph(root) {
+            self.root = Some(root);
             Ok(())
         } else {
-            return Err(format!("Node {} is not in the Graph. Can't Set It to Root", root.unwrap()))
+            Err(GraphError::MissingNode(root))
         }
     }
 
 
-    // Function creates a directed edge of the graph between two nodes
-    pub fn add_edge(&mut self, from: usize, to: usize) -> Result<(), String> {
+    // Function creates an edge of the graph between two nodes with a given payload
+    // Returns the identifier of the new edge
+    pub fn add_edge_with(&mut self, from: usize, to: usize, weight: E) -> Result<EdgeId, GraphError> {
         // Check if both nodes are in the graph
-        if self.in_graph(from) && self.in_graph(to) {
-            // Start and end of the edge must be different nodes
-            // That is the only forbidden case of a loop
-            if to == from {
-                return Err(String::from("Can't Form an Edge From the Node to Itself!"))
-            } else {
-                // Check if edge does not exist yet
-                // Multiple edges from one node to another are forbidden
-                if !self.get_node(from).unwrap().connected().contains(&to) {
-                    self.get_node_mut(from).unwrap().connected_mut().push(to);
-                    Ok(())
-                } else {
-                    return Err(format!("Multiple Edges From Node {} to Node {} are Forbidden!", to, from))
-                }
-            }
-        } else {
-            return Err(String::from("Both Nodes Must Be First Added To The Graph!"))
+        self.check_nodes(from, to)?;
+        // Start and end of the edge must be different nodes unless the policy allows self-loops
+        if to == from && !self.policy.allow_self_loops {
+            return Err(GraphError::SelfLoop(from))
+        }
+        // Multiple edges from one node to another are forbidden unless the policy allows them
+        if !self.policy.allow_parallel_edges && self.stored_from(from, to).is_some() {
+            return Err(GraphError::DuplicateEdge(from, to))
         }
+        let id = self.next_edge;
+        self.next_edge += 1;
+        self.get_node_mut(from).unwrap().push_edge(id, to, weight);
+        self.incoming.entry(to).or_default().push(from);
+        self.edge_sources.insert(id, from);
+        Ok(id)
     }
 
     // Function deletes an edge between two nodes
-    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<(), String> {
+    // If there are parallel edges between the nodes, all of them are deleted
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
         // Check if both nodes are in the graph
-        if self.in_graph(from) && self.in_graph(to) {
-            if let Some(node) = self.get_node_mut(from) {
-                // Check if the edge exists
-                if node.connected().contains(&to) {
-                    node.connected_mut().retain(|el| *el != to);
-                    return Ok(())
-                } else {
-                    return Err(String::from("The Edge Between Given Nodes Does Not Exist!"))
-                }
-            } else {
-                return Err(String::from("Could Not Find the First Given Node in the Graph"))
+        self.check_nodes(from, to)?;
+        // Check if the edge exists
+        self.stored_from(from, to).ok_or(GraphError::MissingEdge(from, to))?;
+        self.remove_stored_edges(from, to);
+        // Parallel undirected edges may be stored in both nodes
+        if !D::is_directed() && from != to {
+            self.remove_stored_edges(to, from);
+        }
+        Ok(())
+    }
+
+    // Function deletes all edges stored in one node that go to another one
+    fn remove_stored_edges(&mut self, from: usize, to: usize) {
+        if let Some(node) = self.get_node_mut(from) {
+            for id in node.remove_edges_to(to) {
+                self.edge_sources.remove(&id);
+            }
+        }
+        self.unlink_incoming(from, to);
+    }
+
+    // Function deletes an edge with a given identifier and returns its payload
+    pub fn remove_edge_by_id(&mut self, id: EdgeId) -> Result<E, GraphError> {
+        let from = self.edge_sources.remove(&id).ok_or(GraphError::MissingEdgeId(id))?;
+        let (to, weight) = self.get_node_mut(from)
+            .and_then(|node| node.remove_edge_id(id))
+            .ok_or(GraphError::MissingEdgeId(id))?;
+        // Only one entry is removed from the reverse index, parallel edges keep theirs
+        if let Some(sources) = self.incoming.get_mut(&to) {
+            if let Some(position) = sources.iter().position(|&source| source == from) {
+                sources.remove(position);
+            }
+            if sources.is_empty() {
+                self.incoming.remove(&to);
             }
+        }
+        Ok(weight)
+    }
+
+    // Function returns the edge with a given identifier as a (from, to, payload) triple
+    pub fn edge(&self, id: EdgeId) -> Option<(usize, usize, &E)> {
+        let from = *self.edge_sources.get(&id)?;
+        let node = self.get_node(from)?;
+        let (to, weight) = node.edge_at(node.edge_position(id)?);
+        Some((from, to, weight))
+    }
+
+    // Function returns the edge with a given identifier with a mutable payload
+    pub fn edge_mut(&mut self, id: EdgeId) -> Option<(usize, usize, &mut E)> {
+        let from = *self.edge_sources.get(&id)?;
+        let node = self.get_node_mut(from)?;
+        let position = node.edge_position(id)?;
+        let (to, weight) = node.edge_at_mut(position);
+        Some((from, to, weight))
+    }
+
+    // Function returns identifiers of all edges between two nodes
+    pub fn edge_ids(&self, from: usize, to: usize) -> Vec<EdgeId> {
+        let between = |from: usize, to: usize| {
+            self.get_node(from).into_iter()
+                .flat_map(|node| node.edges_with_ids())
+                .filter(move |&(_, next, _)| next == to)
+                .map(|(id, _, _)| id)
+        };
+        let mut ids: Vec<EdgeId> = between(from, to).collect();
+        // Undirected edges might be stored the other way round (self-loops are stored once)
+        if !D::is_directed() && from != to {
+            ids.extend(between(to, from));
+        }
+        ids
+    }
+
+    // Function finds in which direction an edge between two nodes is stored
+    // Undirected edge 'a b' might be stored as 'b a'
+    fn stored_from(&self, from: usize, to: usize) -> Option<(usize, usize)> {
+        let stored = |from: usize, to: usize| {
+            self.get_node(from).is_some_and(|node| node.connected().contains(&to))
+        };
+        if stored(from, to) {
+            Some((from, to))
+        } else if !D::is_directed() && stored(to, from) {
+            Some((to, from))
+        } else {
+            None
+        }
+    }
+
+    // Function returns the payload of an edge between two nodes
+    pub fn edge_weight(&self, from: usize, to: usize) -> Option<&E> {
+        let (from, to) = self.stored_from(from, to)?;
+        self.get_node(from)?.edges().find(|&(next, _)| next == to).map(|(_, weight)| weight)
+    }
+
+    // Function returns the mutable payload of an edge between two nodes
+    pub fn edge_weight_mut(&mut self, from: usize, to: usize) -> Option<&mut E> {
+        let (from, to) = self.stored_from(from, to)?;
+        self.get_node_mut(from)?.edges_mut().find(|(next, _)| *next == to).map(|(_, weight)| weight)
+    }
+
+    // Function returns edges of the node as (neighbor, payload) pairs
+    // For directed graphs these are edges going out of the node
+    // For undirected graphs these are all edges of the node
+    pub fn edges(&self, index: usize) -> Option<impl Iterator<Item = (usize, &E)>> {
+        let node = self.get_node(index)?;
+        // Undirected edges stored in other nodes are found through the reverse index
+        let sources: &[usize] = if D::is_directed() {
+            &[]
         } else {
-            Err(String::from("Both Nodes Must Be First Added To The Graph!"))
-        }        
+            self.incoming.get(&index).map_or(&[], |sources| sources.as_slice())
+        };
+        // Each source is visited once, even if it has several edges to the node
+        let mut seen = HashSet::new();
+        let stored_in_others = sources.iter()
+            .filter(move |&&from| from != index && seen.insert(from))
+            .filter_map(move |&from| self.get_node(from).map(|other| (from, other)))
+            .flat_map(move |(from, other)| other.edges().filter(move |&(to, _)| to == index).map(move |(_, weight)| (from, weight)));
+        Some(node.edges().chain(stored_in_others))
+    }
+
+    // Function returns indexes of nodes the edges of the node lead to
+    // For undirected graphs these are all adjacent nodes
+    pub fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges(index).into_iter().flatten().map(|(next, _)| next)
+    }
+
+    // Function removes all edges from one node to another from the reverse index
+    fn unlink_incoming(&mut self, from: usize, to: usize) {
+        if let Some(sources) = self.incoming.get_mut(&to) {
+            sources.retain(|&source| source != from);
+            if sources.is_empty() {
+                self.incoming.remove(&to);
+            }
+        }
+    }
+
+    // Function checks that both ends of an edge are in the graph
+    fn check_nodes(&self, from: usize, to: usize) -> Result<(), GraphError> {
+        for index in [from, to] {
+            if !self.in_graph(index) {
+                return Err(GraphError::MissingNode(index))
+            }
+        }
+        Ok(())
     }
 
     // Function returns a custom iterator over the graph
+    // It starts with the root, or goes over the whole graph if there is no root
     pub fn iterator(&self) -> GraphIter {
-        GraphIter::new(self.root).unwrap()
+        GraphIter::new(self.root).unwrap_or_else(|_| GraphIter::rootless())
+    }
+
+    // Function returns a borrowing iterator of BREADTH-first-search starting with a given node
+    pub fn bfs(&self, start: usize) -> Traversal<'_, T, E, D> {
+        Traversal::new(self, start, Order::Breadth)
+    }
+
+    // Function returns a borrowing iterator of DEPTH-first-search starting with a given node
+    pub fn dfs(&self, start: usize) -> Traversal<'_, T, E, D> {
+        Traversal::new(self, start, Order::Depth)
+    }
+
+    // Function returns a borrowing iterator of POST-ORDER depth-first-search starting with a given node
+    pub fn post_order(&self, start: usize) -> Traversal<'_, T, E, D> {
+        Traversal::new(self, start, Order::PostOrder)
+    }
+
+    // Function returns a borrowing iterator of REVERSE POST-ORDER depth-first-search starting with a given node
+    pub fn reverse_post_order(&self, start: usize) -> Traversal<'_, T, E, D> {
+        Traversal::new(self, start, Order::ReversePostOrder)
+    }
+
+    // Function returns a borrowing iterator of BREADTH-first-search yielding (index, depth, parent)
+    // Nodes further than 'max_depth' edges from the start (if given) are not visited
+    pub fn bfs_depths(&self, start: usize, max_depth: Option<usize>) -> DepthBfs<'_, T, E, D> {
+        DepthBfs::new(self, start, max_depth)
+    }
+
+    // Function groups nodes reachable from the start by their distance from it (in edges)
+    // Level i holds the nodes i edges away in BFS order. The first level is the start itself
+    pub fn levels(&self, start: usize) -> Vec<Vec<usize>> {
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        for (index, depth, _) in self.bfs_depths(start, None) {
+            // BFS yields nodes in order of their depths
+            if depth == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[depth].push(index);
+        }
+        levels
+    }
+
+    // Function returns a borrowing iterator of BREADTH-first-search over the whole graph
+    // Each node comes with the id of its connected component, as numbered by connected_components()
+    pub fn bfs_all(&self) -> Forest<'_, T, E, D> {
+        Forest::new(self, Order::Breadth)
+    }
+
+    // Function returns a borrowing iterator of DEPTH-first-search over the whole graph
+    // Each node comes with the id of its connected component, as numbered by connected_components()
+    pub fn dfs_all(&self) -> Forest<'_, T, E, D> {
+        Forest::new(self, Order::Depth)
     }
 
 }
 
 
 // Implementation of traits for propper output
-impl<T: Display + Debug> Graph<T> {    
+impl<T: Display + Debug, E, D: EdgeType> Graph<T, E, D> {    
     // Function prints the graph
     pub fn print(&self) {
-        println!("\nRoot Node: {}", self.root.unwrap());
+        match self.root {
+            Some(root) => println!("\nRoot Node: {}", root),
+            None => println!("\nRoot Node: Not Set"),
+        }
         // Create an iterator of a graph
         let mut graph_iter = self.iterator();
         // Iterate over the graph and print each node
-        while let Some(i) = graph_iter.next_breadth_search(&self) {
+        while let Some(i) = graph_iter.next_breadth_search(self) {
             if let Some(node) = self.get_node(i) {
                 println!("{}", node);
             } else {
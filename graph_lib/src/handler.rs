@@ -1,12 +1,21 @@
-use super::graph::Graph;
+use super::graph::{Graph, EdgeType};
+use super::iterator::GraphIter;
 use super::node::Node;
-use std::fmt::Display;
+use super::codec::{ValueEncode, ValueCodec};
+use super::error::GraphError;
 use std::fs::File;
-use std::io::{Write, BufReader, BufRead};
+use std::io::{self, Write, BufReader, BufRead, BufWriter};
+use std::path::Path;
+use std::collections::HashSet;
 
 
 // Module of a Graph Handler
 
+// Label marking the root node in TGF. Node value (if any) follows it after a space
+const ROOT_MARKER: &str = "Root";
+// Prefix of a label of a non-root node whose value could be mistaken for the root marker
+const ESCAPE: char = '\\';
+
 pub struct GraphHandler;
 
 impl GraphHandler {
@@ -16,86 +25,168 @@ impl GraphHandler {
 		GraphHandler
 	}
 
-	// Function serializes the graph into Trivial Graph Format
-    pub fn serialize<T: Display>(&self, graph: &Graph<T>, path: &String) -> Result<(), String> {
+	// Function serializes the graph into Trivial Graph Format and writes it into the file
+    pub fn serialize<T, E, D, P, M, N>(&self, graph: &Graph<T, E, D>, path: P) -> Result<(), GraphError>
+    where T: ValueEncode<M>, E: ValueEncode<N>, D: EdgeType, P: AsRef<Path> {
+        let mut output = BufWriter::new(File::create(path)?);
+        self.write_tgf(graph, &mut output)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    // Function serializes the graph into Trivial Graph Format and returns it as a string
+    pub fn to_tgf_string<T, E, D, M, N>(&self, graph: &Graph<T, E, D>) -> Result<String, GraphError>
+    where T: ValueEncode<M>, E: ValueEncode<N>, D: EdgeType {
+        let mut output = Vec::new();
+        self.write_tgf(graph, &mut output)?;
+        String::from_utf8(output).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into())
+    }
+
+	// Function serializes the graph into Trivial Graph Format and writes it into any writer
+    // Values of nodes and payloads of edges are written as their labels
+    pub fn write_tgf<T, E, D, W, M, N>(&self, graph: &Graph<T, E, D>, mut output: W) -> Result<(), GraphError>
+    where T: ValueEncode<M>, E: ValueEncode<N>, D: EdgeType, W: Write {
         
-        let mut iter = graph.iterator();            
-        let mut output = File::create(path).expect("Could Not Create a File to Write Into");
-        // Iterate over all nodes and write each node data into the file
-        while let Some(i) = iter.next_breadth_search(graph) {
+        // Edge to a node that is not in the graph could not be read back
+        // It is reported before anything is written
+        for node in graph.nodes() {
+            if let Some(&missing) = node.connected().iter().find(|&&to| !graph.in_graph(to)) {
+                return Err(GraphError::MissingNode(missing));
+            }
+        }
+        // Nodes reachable from the root go first, all the rest follow them
+        let order = node_order(graph);
+        // Write each node data
+        for &i in order.iter() {
             if let Some(node) = graph.get_node(i) {
-            	// Mark the root of the graph in TGF with "Root"
-            	if graph.root.unwrap() == node.index {
-                	writeln!(output, "{} Root", node.index).expect("Could Not Write a Node to File!");
-            	} else {
-                	writeln!(output, "{}", node.index).expect("Could Not Write a Node to File!");
-            	}
+                // Value of a node is written as its label
+                let label = single_line(node.value.encode(), || format!("Value of Node {}", node.index))?;
+                // Rootless graph has no node marked as the root
+                let is_root = graph.root == Some(node.index);
+                writeln!(output, "{}", node_line(node.index, &label, is_root))?;
             } else {
-                return Err(String::from("Could Not Find a Node!"));
+                return Err(GraphError::MissingNode(i));
             }
         }
         // Separator between strings of nodes and strings of edges
-        writeln!(output, "#").expect("Could Not Write a Separator to File!");
-        
-        // Reset the iterator to start iterating again
-        iter.reset(graph.root);
+        writeln!(output, "#")?;
 
-        // Iterate over all nodes and write each pair of connected nodes
-        while let Some(i) = iter.next_breadth_search(graph) {
+        // Write each pair of connected nodes in the same order
+        for &i in order.iter() {
             if let Some(node) = graph.get_node(i) {
-                for another in node.connected().iter() {
-                    // No labels for edges are written into the file
-                    writeln!(output, "{} {}", node.index, another).expect("Could Not Write an Edge to File!");
+                for (another, weight) in node.edges() {
+                    // Payload of an edge is written as its label
+                    let label = single_line(weight.encode(), || format!("Payload of Edge {} {}", node.index, another))?;
+                    if label.is_empty() {
+                        writeln!(output, "{} {}", node.index, another)?;
+                    } else {
+                        writeln!(output, "{} {} {}", node.index, another, label)?;
+                    }
                 }
             } else {
-                return Err(String::from("Could Not Find a Node!"));
+                return Err(GraphError::MissingNode(i));
             }
         }
 
         Ok(())
     }
 
-    // Function deserializes the graph from Trivial Graph Format
-    pub fn deserialize<T: Default>(&self, graph: &mut Graph<T>, path: &String) -> Result<(), String> {
+    // Function deserializes the graph from the file in Trivial Graph Format
+    pub fn deserialize<T, E, D, P, M, N>(&self, graph: &mut Graph<T, E, D>, path: P) -> Result<(), GraphError>
+    where T: ValueCodec<M>, E: ValueCodec<N>, D: EdgeType, P: AsRef<Path> {
+        let input = File::open(path)?;
+        self.read_tgf(graph, BufReader::new(input))
+    }
+
+    // Function deserializes the graph from a string in Trivial Graph Format
+    pub fn from_tgf_str<T, E, D, M, N>(&self, graph: &mut Graph<T, E, D>, text: &str) -> Result<(), GraphError>
+    where T: ValueCodec<M>, E: ValueCodec<N>, D: EdgeType {
+        self.read_tgf(graph, text.as_bytes())
+    }
+
+    // Function deserializes the graph from any reader in Trivial Graph Format
+    // Labels of nodes and edges are parsed into values of nodes and payloads of edges
+    // Any malformed line is reported with its number and the offending text
+    pub fn read_tgf<T, E, D, R, M, N>(&self, graph: &mut Graph<T, E, D>, mut buf: R) -> Result<(), GraphError>
+    where T: ValueCodec<M>, E: ValueCodec<N>, D: EdgeType, R: BufRead {
 
-        let input = File::open(path).expect("Could Not Open a File to Read From");
-        let buf = BufReader::new(input);
         // Indicates if reading edges or nodes
         let mut edges = false;
-        // Read all lines and iterate over them
-        for line in buf.lines().map(|line| line.unwrap()) {
-            // Split the line into parts with spaces
-            let parts: Vec<&str> = line.split(" ").collect();
+        // Number of the line where the root was found
+        let mut root_line: Option<usize> = None;
+        let mut bytes = Vec::new();
+        let mut number = 0;
+        // Read all lines one by one
+        loop {
+            bytes.clear();
+            if buf.read_until(b'\n', &mut bytes)? == 0 {
+                break;
+            }
+            number += 1;
+            let line = decode_line(&bytes, number)?;
+            // Empty lines are skipped
+            if line.is_empty() {
+                continue;
+            }
             // Reading nodes
             if !edges {
                 // Until the '#'
-                if parts.get(0).unwrap() == &"#" {
+                if line == "#" {
                     edges = true;
                     continue;
                 }
-                // First part is an index
-                let index: usize = parts.get(0).unwrap().parse().unwrap();
-                // Second part is all the rest
-                let label= parts[1..].join(" ");
-
-                // WARNING!
-
-                // Node values are NOT listed in the TGF file. That is why type of values of nodes
-                // is infered from the type of the graph what will include these nodes.
-                // Each node gets a default value of it;s type.
-                // Only types implementing 'Default' are allowed.
-                let node = Node::new(index, Default::default(), None);
-                graph.add_node(node)?;
+                // First part is an index, second part is all the rest
+                let (index_text, label) = line.split_once(' ').unwrap_or((line, &line[line.len()..]));
+                let index = parse_index(index_text, number, 1)?;
+                let (is_root, label) = parse_label(label);
+                // The label (without the root marker) holds the value of a node
+                let value = T::decode(label).map_err(|reason| GraphError::Parse {
+                    line: number,
+                    column: column(line, label),
+                    reason: format!("Invalid Value of Node {}: {}", index, reason),
+                })?;
+                let node = Node::with_edges(index, value, Vec::new());
+                graph.add_node(node).map_err(|err| parse_error(number, 1, line, err))?;
                 // One of the nodes must be the root
-                if label == String::from("Root") {
+                if is_root {
+                    // Only one node can be marked as the root
+                    if let Some(first) = root_line {
+                        return Err(GraphError::Parse {
+                            line: number,
+                            column: index_text.len() + 2,
+                            reason: format!("Duplicate Root `{}` (First Root is at Line {})", line, first),
+                        });
+                    }
+                    root_line = Some(number);
                     // Set the root of a graph
-                    graph.set_root(Some(index))?;
+                    graph.set_root(Some(index)).map_err(|err| parse_error(number, 1, line, err))?;
                 }
             // Reading edges
             } else {
-                let from = parts.get(0).unwrap().parse().unwrap();
-                let to = parts.get(1).unwrap().parse().unwrap();
-                graph.add_edge(from, to)?;
+                // Two first parts are indexes, the rest is a label
+                let mut parts = line.splitn(3, ' ');
+                let from_text = parts.next().unwrap_or(line);
+                let to_text = parts.next().ok_or_else(|| GraphError::Parse {
+                    line: number,
+                    column: line.len() + 1,
+                    reason: format!("Edge `{}` Must Have Two Node Indexes", line),
+                })?;
+                let from = parse_index(from_text, number, 1)?;
+                let to = parse_index(to_text, number, column(line, to_text))?;
+                let label = parts.next().unwrap_or(&line[line.len()..]);
+                let weight = E::decode(label).map_err(|reason| GraphError::Parse {
+                    line: number,
+                    column: column(line, label),
+                    reason: format!("Invalid Payload of Edge {} {}: {}", from, to, reason),
+                })?;
+                graph.add_edge_with(from, to, weight).map_err(|err| {
+                    // Point at the index of the missing node
+                    let at = match err {
+                        GraphError::MissingNode(missing) if missing != from => column(line, to_text),
+                        _ => 1,
+                    };
+                    parse_error(number, at, line, err)
+                })?;
             }
         }
 
@@ -104,3 +195,89 @@ impl GraphHandler {
 
 }
 
+
+// Function checks that a label fits into a single line
+// 'what' describes the owner of the label for the error message
+fn single_line(label: String, what: impl FnOnce() -> String) -> Result<String, GraphError> {
+    if label.contains(['\n', '\r']) {
+        let reason = format!("{} Can Not be Written as a Single Line Label", what());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, reason).into());
+    }
+    Ok(label)
+}
+
+// Function turns raw bytes of a line into a string without the line break
+fn decode_line(bytes: &[u8], number: usize) -> Result<&str, GraphError> {
+    let line = std::str::from_utf8(bytes).map_err(|err| GraphError::Parse {
+        line: number,
+        column: err.valid_up_to() + 1,
+        reason: format!("Line `{}` is not Valid UTF-8", String::from_utf8_lossy(bytes).trim_end()),
+    })?;
+    Ok(line.trim_end_matches(['\n', '\r']))
+}
+
+// Function parses an index of a node
+fn parse_index(text: &str, line: usize, column: usize) -> Result<usize, GraphError> {
+    text.parse().map_err(|_| GraphError::Parse {
+        line,
+        column,
+        reason: format!("Invalid Node Index `{}`", text),
+    })
+}
+
+// Function wraps an error of the graph into the parse error at the given position
+// The text of the line goes first in the reason, like in the other parse errors
+fn parse_error(line: usize, column: usize, text: &str, err: GraphError) -> GraphError {
+    GraphError::Parse { line, column, reason: format!("`{}`: {}", text, err) }
+}
+
+// Function finds the column (starting with 1) where a part of the line begins
+fn column(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+
+// Function returns indexes of all nodes of the graph
+// Nodes reachable from the root go first (in BFS order), then all the rest (in arena order)
+fn node_order<T, E, D: EdgeType>(graph: &Graph<T, E, D>) -> Vec<usize> {
+    let mut order = Vec::with_capacity(graph.arena.len());
+    if let Ok(mut iter) = GraphIter::new(graph.root) {
+        while let Some(i) = iter.next_breadth_search(graph) {
+            order.push(i);
+        }
+    }
+    let reached: HashSet<usize> = order.iter().copied().collect();
+    order.extend(graph.arena.iter().map(|node| node.index).filter(|i| !reached.contains(i)));
+    order
+}
+
+// Function builds a TGF line of a node out of its index and label
+fn node_line(index: usize, label: &str, is_root: bool) -> String {
+    if is_root {
+        // Root marker goes first, the value follows it
+        if label.is_empty() {
+            format!("{} {}", index, ROOT_MARKER)
+        } else {
+            format!("{} {} {}", index, ROOT_MARKER, label)
+        }
+    } else if label.is_empty() {
+        format!("{}", index)
+    } else if parse_label(label).0 || label.starts_with(ESCAPE) {
+        // Value looks like a root marker (or an escaped label) so it gets escaped
+        format!("{} {}{}", index, ESCAPE, label)
+    } else {
+        format!("{} {}", index, label)
+    }
+}
+
+// Function splits a TGF label of a node into the root marker and the value label
+fn parse_label(label: &str) -> (bool, &str) {
+    if label == ROOT_MARKER {
+        // Empty value is still taken from the label to keep its position in the line
+        return (true, &label[label.len()..]);
+    }
+    if let Some(value) = label.strip_prefix(ROOT_MARKER).and_then(|rest| rest.strip_prefix(' ')) {
+        return (true, value);
+    }
+    (false, label.strip_prefix(ESCAPE).unwrap_or(label))
+}
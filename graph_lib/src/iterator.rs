@@ -1,103 +1,347 @@
-use super::graph::Graph;
-use std::fmt::Display;
+use super::graph::{Graph, EdgeType, Directed};
+use super::node::Node;
+use super::error::GraphError;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 // Module of a custom iterator
-// Built-in Iterator trait doesn't fit current task.
+// Built-in Iterator trait doesn't fit the visitor-style GraphIter.
+// Borrowing iterators (Traversal, Forest) implement it on top of GraphIter.
 
 pub struct GraphIter {
-    // Node indexes are stored on the stack
-    stack: Vec<usize>,
-    // Vector holds the indexes of nodes that have already been visited
-    visited: Vec<usize>,
+    // Node indexes waiting to be processed
+    // BFS takes them from the front (queue), DFS takes them from the back (stack)
+    stack: VecDeque<usize>,
+    // Set holds the indexes of nodes that have already been visited
+    visited: HashSet<usize>,
+    // Rootless iterator restarts from the first unvisited node of the arena when the stack runs out
+    // This is the position in the arena to look for it from. None for an iterator with a root
+    restart: Option<usize>,
+    // Number of nodes removed from the graph when 'restart' was found
+    removals: usize,
+    // Number of traversal trees started so far (each start or restart begins a new one)
+    trees: usize,
+    // Nodes already returned by the post-order search
+    finished: HashSet<usize>,
+    // Nodes of the reverse post-order that are not returned yet (from the last to the first)
+    // Computed on the first call of next_reverse_post_order()
+    post_order: Option<Vec<usize>>,
 }
 
 impl GraphIter {
 
     // Constructor of the iterator 
-    pub fn new(root: Option<usize>) -> Result<Self, String> {
+    pub fn new(root: Option<usize>) -> Result<Self, GraphError> {
         // If there is a root - stack starts with it
         if let Some(root) = root {
             Ok(
                 GraphIter {
-                    stack: vec![root],
-                    visited: vec![],
+                    stack: VecDeque::from([root]),
+                    visited: HashSet::new(),
+                    restart: None,
+                    removals: 0,
+                    trees: 1,
+                    finished: HashSet::new(),
+                    post_order: None,
                 }
             )
         // If there is no root - stack is empty
         } else {
-            Err(String::from("Please, Provide a Root Node for the Iterator!"))
+            Err(GraphError::RootNotSet)
         }
     }
 
+    // Constructor of the iterator over the whole graph
+    // Traversal starts with the first node of the arena. When no more nodes can be reached,
+    // it starts again from the next node that has not been visited yet
+    pub fn rootless() -> Self {
+        GraphIter {
+            stack: VecDeque::new(),
+            visited: HashSet::new(),
+            restart: Some(0),
+            removals: 0,
+            trees: 0,
+            finished: HashSet::new(),
+            post_order: None,
+        }
+    }
+
+    // Function returns the id of the traversal tree the last returned node belongs to
+    // Trees are numbered from 0 in the order they are started. Iterator with a root has a single tree
+    // A tree is not a connected component: in a directed graph a node that only has edges into
+    // an earlier tree starts a new one. For undirected graphs trees are the connected components
+    pub fn tree(&self) -> usize {
+        self.trees.saturating_sub(1)
+    }
+
     // Function resets the iterator
     pub fn reset(&mut self, root: Option<usize>) {
         if let Some(root) = root {
             // Reset the stack and the visited nodes list
             self.visited.clear();
-            self.stack = vec![root];
+            self.stack.clear();
+            self.stack.push_back(root);
+            self.restart = None;
+            self.trees = 1;
+            self.finished.clear();
+            self.post_order = None;
         } else {
             panic!("Please, Provide a Root To Reset the Iterator!");
         }
     }
 
-    // Next two functions implement a Visitor Pattern. They only borrow a graph when they are beeing called
+    // Next functions implement a Visitor Pattern. They only borrow a graph when they are beeing called
     // Between the calls the graph can be modified in any way. A graph to borrow is passed as the second parameter.
+    // One iterator should only be used with one of them (until it is reset).
     
-    // Function returns the next item from the iterator of BREADTH-first-search
-    pub fn next_breadth_search<T: Display>(&mut self, graph: &Graph<T>) -> Option<usize> {
-        // remove() might panic, so we have to check for the size of the stack
-        while self.stack.len() >= 1 {
-            // Get the next index from the stack
-            let node_index = self.stack.remove(0);
+    // Function returns the next item from the iterator in a given order
+    pub fn next_in<T, E, D: EdgeType>(&mut self, order: Order, graph: &Graph<T, E, D>) -> Option<usize> {
+        match order {
+            Order::Breadth => self.next_breadth_search(graph),
+            Order::Depth => self.next_depth_search(graph),
+            Order::PostOrder => self.next_post_order(graph),
+            Order::ReversePostOrder => self.next_reverse_post_order(graph),
+        }
+    }
 
+    // Function returns the next item from the iterator of BREADTH-first-search
+    pub fn next_breadth_search<T, E, D: EdgeType>(&mut self, graph: &Graph<T, E, D>) -> Option<usize> {
+        // Get the next index from the front of the queue
+        while let Some(node_index) = self.stack.pop_front().or_else(|| self.next_tree(graph)) {
             // Only process nodes that have not been visited yet
-            if self.visited.contains(&node_index) {
+            if !self.visited.insert(node_index) {
                 continue;
             }
-            self.visited.push(node_index);
 
-            // Get the node with that index from the arena
-            if let Some(node) = graph.get_node(node_index) {
-                // Add it's neighbours to the stack
-                for node in node.connected().iter(){
-                        self.stack.push(*node);
+            // Check that the node with that index is in the arena
+            if graph.in_graph(node_index) {
+                // Add it's neighbours that have not been visited yet to the queue
+                for next in graph.neighbors(node_index) {
+                    // Edges to nodes that are not in the graph are skipped
+                    if graph.in_graph(next) && !self.visited.contains(&next) {
+                        self.stack.push_back(next);
+                    }
                 }
                 return Some(node_index)
             } else {
                 panic!("Could Not Find a Node!");
             }
         }
-        return None
+        None
     }
 
     // Function returns the next item from the iterator of DEPTH-first-search
-    pub fn next_depth_search<T: Display>(&mut self, graph: &Graph<T>) -> Option<usize> {
+    pub fn next_depth_search<T, E, D: EdgeType>(&mut self, graph: &Graph<T, E, D>) -> Option<usize> {
 
-        // Get the next index from the stack
-        while let Some(node_index) = self.stack.pop() {
+        // Get the next index from the back of the stack
+        while let Some(node_index) = self.stack.pop_back().or_else(|| self.next_tree(graph)) {
             // Only process nodes that have not been visited yet
-            if self.visited.contains(&node_index) {
+            if !self.visited.insert(node_index) {
                 continue;
             }
-            self.visited.push(node_index);
-
-            // Get the node with that index from the arena
-            if let Some(node) = graph.get_node(node_index) {
-                // Add it's neighbours to the stack
-                let mut clone = node.connected().clone();
-                // Reverse the stack to process the leftmost edge first (human-readible)
-                clone.reverse();
-                for node in clone.iter() {
-                        self.stack.push(*node);
+
+            // Check that the node with that index is in the arena
+            if graph.in_graph(node_index) {
+                // Add it's neighbours that have not been visited yet to the stack
+                let start = self.stack.len();
+                for next in graph.neighbors(node_index) {
+                    // Edges to nodes that are not in the graph are skipped
+                    if graph.in_graph(next) && !self.visited.contains(&next) {
+                        self.stack.push_back(next);
+                    }
                 }
+                // Reverse them to process the leftmost edge first (human-readible)
+                self.stack.make_contiguous()[start..].reverse();
                 return Some(node_index)
             } else {
                 panic!("Could Not Find a Node!");
             }
         }
-        return None
+        None
+    }
+
+    // Function returns the next item from the iterator of POST-ORDER depth-first-search
+    // A node is returned after all nodes reachable from it (children before parents)
+    // An edge back to a node that is still in progress (a cycle) is not followed
+    pub fn next_post_order<T, E, D: EdgeType>(&mut self, graph: &Graph<T, E, D>) -> Option<usize> {
+        loop {
+            // Look at the node on top of the stack without taking it
+            let node_index = match self.stack.back() {
+                Some(&node_index) => node_index,
+                None => {
+                    let start = self.next_tree(graph)?;
+                    self.stack.push_back(start);
+                    start
+                }
+            };
+            // First time on top: its neighbours that have not been visited yet go above it
+            if self.visited.insert(node_index) {
+                if !graph.in_graph(node_index) {
+                    panic!("Could Not Find a Node!");
+                }
+                let start = self.stack.len();
+                for next in graph.neighbors(node_index) {
+                    // Edges to nodes that are not in the graph are skipped
+                    if graph.in_graph(next) && !self.visited.contains(&next) {
+                        self.stack.push_back(next);
+                    }
+                }
+                // Reverse them to process the leftmost edge first (human-readible)
+                self.stack.make_contiguous()[start..].reverse();
+                continue;
+            }
+            // Second time on top: all its neighbours are done
+            // A node may be on the stack several times, it is only returned once
+            self.stack.pop_back();
+            if self.finished.insert(node_index) {
+                return Some(node_index);
+            }
+        }
+    }
+
+    // Function returns the next item from the iterator of REVERSE POST-ORDER depth-first-search
+    // A node is returned before all nodes reachable from it, unless they are on a cycle with it
+    // The whole order is found on the first call, later changes of the graph don't affect it
+    pub fn next_reverse_post_order<T, E, D: EdgeType>(&mut self, graph: &Graph<T, E, D>) -> Option<usize> {
+        if self.post_order.is_none() {
+            let mut order = Vec::new();
+            while let Some(node_index) = self.next_post_order(graph) {
+                order.push(node_index);
+            }
+            self.post_order = Some(order);
+        }
+        // The last node of the post-order goes first
+        self.post_order.as_mut()?.pop()
+    }
+
+    // Function finds the node to start a new traversal tree with (only for a rootless iterator)
+    fn next_tree<T, E, D: EdgeType>(&mut self, graph: &Graph<T, E, D>) -> Option<usize> {
+        // Nodes before the saved position are visited. If nodes were removed since then,
+        // others could have been moved before it, so the whole arena is looked through again
+        let saved = self.restart?;
+        let start = if graph.removals == self.removals { saved } else { 0 };
+        let found = graph.arena.iter().skip(start).position(|node| !self.visited.contains(&node.index))?;
+        self.restart = Some(start + found + 1);
+        self.removals = graph.removals;
+        self.trees += 1;
+        Some(graph.arena[start + found].index)
+    }
+
+}
+
+
+// Borrowing iterators over the graph
+// Unlike the visitor-style functions above they keep the graph borrowed
+// for their whole lifetime, but work with 'for' loops and iterator adapters
+
+// Order in which a traversal returns the nodes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    // BREADTH-first-search
+    Breadth,
+    // DEPTH-first-search
+    Depth,
+    // POST-ORDER depth-first-search
+    PostOrder,
+    // REVERSE POST-ORDER depth-first-search
+    ReversePostOrder,
+}
+
+// Iterator of a traversal from a given node yielding indexes and nodes
+pub struct Traversal<'g, T, E = (), D = Directed> {
+    graph: &'g Graph<T, E, D>,
+    // No iterator if the start node is not in the graph
+    iter: Option<GraphIter>,
+    order: Order,
+}
+
+// Iterator over the whole graph yielding ids of connected components, indexes and nodes
+// It restarts from each node that has not been visited yet (see GraphIter::rootless)
+pub struct Forest<'g, T, E = (), D = Directed> {
+    graph: &'g Graph<T, E, D>,
+    iter: GraphIter,
+    // Id of the connected component of each node (position in Graph::connected_components())
+    component_of: HashMap<usize, usize>,
+    order: Order,
+}
+
+// Iterator of BREADTH-first-search yielding indexes, depths and parents of nodes
+// Depth is the number of edges between the start and the node. The start has no parent
+pub struct DepthBfs<'g, T, E = (), D = Directed> {
+    graph: &'g Graph<T, E, D>,
+    // Nodes waiting to be processed with their depths and parents
+    queue: VecDeque<(usize, usize, Option<usize>)>,
+    // Nodes are marked as soon as they are queued, so each one gets the parent that found it first
+    queued: HashSet<usize>,
+    // Edges of nodes at this depth are not followed
+    max_depth: Option<usize>,
+}
+
+impl<'g, T, E, D: EdgeType> Traversal<'g, T, E, D> {
+    // Constructor of the iterator. Traversal starts with a given node
+    pub fn new(graph: &'g Graph<T, E, D>, start: usize, order: Order) -> Self {
+        let iter = if graph.in_graph(start) { GraphIter::new(Some(start)).ok() } else { None };
+        Traversal { graph, iter, order }
+    }
+}
+
+impl<'g, T, E, D: EdgeType> Forest<'g, T, E, D> {
+    // Constructor of the iterator
+    pub fn new(graph: &'g Graph<T, E, D>, order: Order) -> Self {
+        let component_of = graph.connected_components()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(id, members)| members.into_iter().map(move |member| (member, id)))
+            .collect();
+        Forest { graph, iter: GraphIter::rootless(), component_of, order }
+    }
+}
+
+impl<'g, T, E, D: EdgeType> DepthBfs<'g, T, E, D> {
+    // Constructor of the iterator. Traversal starts with a given node
+    // and doesn't go further than 'max_depth' edges from it (if given)
+    pub fn new(graph: &'g Graph<T, E, D>, start: usize, max_depth: Option<usize>) -> Self {
+        let mut queue = VecDeque::new();
+        let mut queued = HashSet::new();
+        if graph.in_graph(start) {
+            queue.push_back((start, 0, None));
+            queued.insert(start);
+        }
+        DepthBfs { graph, queue, queued, max_depth }
     }
+}
+
+impl<'g, T, E, D: EdgeType> Iterator for Traversal<'g, T, E, D> {
+    type Item = (usize, &'g Node<T, E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+        let index = self.iter.as_mut()?.next_in(self.order, graph)?;
+        graph.get_node(index).map(|node| (index, node))
+    }
+}
 
+impl<'g, T, E, D: EdgeType> Iterator for Forest<'g, T, E, D> {
+    type Item = (usize, usize, &'g Node<T, E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+        let index = self.iter.next_in(self.order, graph)?;
+        graph.get_node(index).map(|node| (self.component_of[&index], index, node))
+    }
 }
 
+impl<'g, T, E, D: EdgeType> Iterator for DepthBfs<'g, T, E, D> {
+    type Item = (usize, usize, Option<usize>);
 
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, depth, parent) = self.queue.pop_front()?;
+        if self.max_depth.is_none_or(|max_depth| depth < max_depth) {
+            for next in self.graph.neighbors(index) {
+                if self.graph.in_graph(next) && self.queued.insert(next) {
+                    self.queue.push_back((next, depth + 1, Some(index)));
+                }
+            }
+        }
+        Some((index, depth, parent))
+    }
+}
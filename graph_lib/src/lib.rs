@@ -2,12 +2,18 @@ pub mod node;
 pub mod graph;
 pub mod iterator;
 pub mod handler;
+pub mod codec;
+pub mod error;
+pub mod ordering;
+pub mod paths;
+pub mod components;
+pub mod visitor;
 
 
 #[cfg(test)]
 mod tests {
 
-    use super::{node::Node, graph::Graph, iterator::GraphIter, handler::GraphHandler};
+    use super::{node::Node, graph::{Graph, UnGraph, GraphPolicy}, iterator::GraphIter, handler::GraphHandler, error::GraphError, codec::ValueCodec, visitor::{DfsEvent, Control}};
 
     // Tests for Node
 
@@ -84,6 +90,176 @@ mod tests {
     }
 
 
+    #[test]
+    pub fn lookup_after_removing_nodes() {
+        let mut graph = Graph::<usize>::new();
+        for index in 0..10 {
+            graph.add_node(Node::new(index * 10, index, None)).unwrap();
+        }
+
+        graph.remove_node(0).unwrap();
+        graph.remove_node(50).unwrap();
+        graph.remove_node(90).unwrap();
+
+        assert_eq!(graph.len(), 7);
+        for index in [0, 50, 90] {
+            assert!(!graph.in_graph(index));
+            assert!(graph.get_node(index).is_none());
+        }
+        for index in [1, 2, 3, 4, 6, 7, 8] {
+            assert_eq!(graph.get_node(index * 10).unwrap().value, index);
+        }
+        graph.get_node_mut(80).unwrap().change_value(800);
+        assert_eq!(graph.get_node(80).unwrap().value, 800);
+        assert_eq!(graph.nodes().count(), 7);
+    }
+
+    #[test]
+    pub fn remove_node_with_edges() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(666,"Text", Some(vec![4, 3]))).unwrap();
+        graph.add_node(Node::new(4,"Middle", Some(vec![3]))).unwrap();
+        graph.add_node(Node::new(3,"Text", None)).unwrap();
+        graph.set_root(Some(666)).unwrap();
+
+        let removed = graph.remove_node(4).unwrap();
+        assert_eq!(removed.value, "Middle");
+        assert_eq!(graph.get_node(666).unwrap().connected(), &vec![3]);
+
+        // Traversal must not stumble upon the removed node
+        let mut iterator = graph.iterator();
+        let mut visited = Vec::new();
+        while let Some(index) = iterator.next_breadth_search(&graph) {
+            visited.push(index);
+        }
+        assert_eq!(visited, vec![666, 3]);
+
+        // Edge to the removed node can be created again once it's back
+        graph.add_node(Node::new(4,"Middle", None)).unwrap();
+        graph.add_edge(666, 4).unwrap();
+        assert_eq!(graph.get_node(666).unwrap().connected(), &vec![3, 4]);
+    }
+
+    #[test]
+    pub fn remove_root_node() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(666,"Text", Some(vec![4]))).unwrap();
+        graph.add_node(Node::new(4,"Text", Some(vec![666]))).unwrap();
+        graph.set_root(Some(666)).unwrap();
+
+        graph.remove_node(666).unwrap();
+        assert_eq!(graph.root, None);
+        assert!(graph.get_node(4).unwrap().connected().is_empty());
+    }
+
+    #[test]
+    pub fn remove_parallel_undirected_edges() {
+        let mut graph: UnGraph<&str, i32> = UnGraph::with_policy(GraphPolicy::multigraph());
+        graph.add_node(Node::with_edges(1, "One", Vec::new())).unwrap();
+        graph.add_node(Node::with_edges(2, "Two", Vec::new())).unwrap();
+        graph.add_edge_with(1, 2, 5).unwrap();
+        graph.add_edge_with(2, 1, 6).unwrap();
+        graph.remove_edge(1, 2).unwrap();
+        assert_eq!(graph.edge_weight(1, 2), None);
+        assert_eq!(graph.edge_weight(2, 1), None);
+        assert!(graph.edge_ids(1, 2).is_empty());
+        assert_eq!(graph.neighbors(1).count(), 0);
+        assert_eq!(graph.in_degree(1), Some(0));
+        assert_eq!(graph.in_degree(2), Some(0));
+    }
+
+    #[test]
+    pub fn add_node_undirected_edge_once() {
+        let mut graph: UnGraph<&str> = UnGraph::default();
+        graph.add_node(Node::new(1, "One", Some(vec![2]))).unwrap();
+        assert!(matches!(graph.add_node(Node::new(2, "Two", Some(vec![1]))), Err(GraphError::DuplicateEdge(2, 1))));
+        graph.add_node(Node::new(2, "Two", None)).unwrap();
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![2]);
+
+        // Multigraph keeps both edges
+        let mut graph: UnGraph<&str> = UnGraph::with_policy(GraphPolicy::multigraph());
+        graph.add_node(Node::new(1, "One", Some(vec![2]))).unwrap();
+        graph.add_node(Node::new(2, "Two", Some(vec![1]))).unwrap();
+        assert_eq!(graph.edge_ids(1, 2).len(), 2);
+    }
+
+    #[test]
+    pub fn add_node_follows_policy() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new(2, "Two", None)).unwrap();
+        assert!(matches!(graph.add_node(Node::new(1, "One", Some(vec![1, 2]))), Err(GraphError::SelfLoop(1))));
+        assert!(matches!(graph.add_node(Node::new(1, "One", Some(vec![2, 2]))), Err(GraphError::DuplicateEdge(1, 2))));
+        assert!(!graph.in_graph(1));
+        assert_eq!(graph.in_degree(2), Some(0));
+
+        // Graph built through the public API can be read back after writing
+        graph.add_node(Node::new(1, "One", Some(vec![2]))).unwrap();
+        graph.set_root(Some(1)).unwrap();
+        let handler = GraphHandler::new();
+        let mut fresh_graph: Graph<String> = Graph::new();
+        handler.from_tgf_str(&mut fresh_graph, &handler.to_tgf_string(&graph).unwrap()).unwrap();
+        assert_eq!(fresh_graph.successors(1), Some(&[2][..]));
+
+        let mut graph: Graph<&str> = Graph::with_policy(GraphPolicy::multigraph());
+        graph.add_node(Node::new(1, "One", Some(vec![1, 2, 2]))).unwrap();
+        assert_eq!(graph.out_degree(1), Some(3));
+    }
+
+    #[test]
+    pub fn insert_allocates_fresh_indexes() {
+        let mut graph = Graph::new();
+
+        let first = graph.insert("First").unwrap();
+        let second = graph.insert("Second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(graph.get_node(second).unwrap().value, "Second");
+
+        // Explicit indexes are still allowed and never given out again
+        graph.add_node(Node::new(100, "Loaded", None)).unwrap();
+        let third = graph.insert("Third").unwrap();
+        assert!(third > 100);
+
+        // Indexes of removed nodes are not reused
+        graph.remove_node(third).unwrap();
+        let fourth = graph.insert("Fourth").unwrap();
+        assert!(fourth > third);
+        graph.add_edge(first, fourth).unwrap();
+        assert_eq!(graph.len(), 4);
+
+        // No index is left after the largest one
+        graph.add_node(Node::new(usize::MAX, "Last", None)).unwrap();
+        assert!(matches!(graph.insert("Fifth"), Err(GraphError::NoFreeIndex)));
+        assert_eq!(graph.len(), 5);
+    }
+
+    #[test]
+    pub fn predecessors_and_degrees() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(1,"Text", Some(vec![3]))).unwrap();
+        graph.add_node(Node::new(2,"Text", None)).unwrap();
+        graph.add_node(Node::new(3,"Text", None)).unwrap();
+        graph.add_edge(2, 3).unwrap();
+        graph.add_edge(3, 1).unwrap();
+
+        assert_eq!(graph.predecessors(3), Some(&[1, 2][..]));
+        assert_eq!(graph.in_degree(3), Some(2));
+        assert_eq!(graph.out_degree(3), Some(1));
+        assert_eq!(graph.in_degree(2), Some(0));
+        assert_eq!(graph.in_degree(5), None);
+        assert_eq!(graph.incoming_edges(1).unwrap().collect::<Vec<_>>(), vec![(3, 1)]);
+
+        graph.remove_edge(2, 3).unwrap();
+        assert_eq!(graph.predecessors(3), Some(&[1][..]));
+
+        graph.remove_node(1).unwrap();
+        assert_eq!(graph.in_degree(3), Some(0));
+        assert_eq!(graph.out_degree(3), Some(0));
+    }
+
     #[test]
     #[should_panic]
     pub fn try_set_root_none() {
@@ -140,6 +316,149 @@ mod tests {
         graph.add_edge(666, 666).unwrap();
     }
 
+    #[test]
+    pub fn structured_graph_errors() {
+        let mut graph = Graph::<&str>::new();
+        graph.add_node(Node::new(666,"Text", None)).unwrap();
+        graph.add_node(Node::new(777,"Text", None)).unwrap();
+        graph.add_edge(666, 777).unwrap();
+
+        assert!(matches!(graph.add_node(Node::new(666,"Text", None)), Err(GraphError::DuplicateNode(666))));
+        assert!(matches!(graph.remove_node(4), Err(GraphError::MissingNode(4))));
+        assert!(matches!(graph.set_root(None), Err(GraphError::RootNotSet)));
+        assert!(matches!(graph.set_root(Some(4)), Err(GraphError::MissingNode(4))));
+        assert!(matches!(graph.add_edge(666, 666), Err(GraphError::SelfLoop(666))));
+        assert!(matches!(graph.add_edge(666, 777), Err(GraphError::DuplicateEdge(666, 777))));
+        assert!(matches!(graph.add_edge(666, 4), Err(GraphError::MissingNode(4))));
+        assert!(matches!(graph.remove_edge(777, 666), Err(GraphError::MissingEdge(777, 666))));
+        assert!(matches!(GraphIter::new(None), Err(GraphError::RootNotSet)));
+    }
+
+    #[test]
+    pub fn graph_error_messages() {
+        assert_eq!(GraphError::DuplicateNode(4).to_string(), "Node 4 is Already in The Graph");
+        let err = GraphError::Parse { line: 3, column: 5, reason: String::from("Invalid Index") };
+        assert_eq!(err.to_string(), "Parse Error at Line 3, Column 5: Invalid Index");
+    }
+
+    #[test]
+    pub fn weighted_edges() {
+        let mut graph: Graph<&str, f64> = Graph::default();
+
+        graph.add_node(Node::with_edges(1, "Gateway", vec![(2, 4.5)])).unwrap();
+        graph.add_node(Node::with_edges(2, "Service", vec![])).unwrap();
+        graph.add_node(Node::with_edges(3, "Cache", vec![])).unwrap();
+        graph.add_edge_with(2, 3, 0.5).unwrap();
+        graph.add_edge_with(1, 3, 12.0).unwrap();
+
+        assert_eq!(graph.edge_weight(1, 2), Some(&4.5));
+        assert_eq!(graph.edge_weight(2, 3), Some(&0.5));
+        assert_eq!(graph.edge_weight(3, 1), None);
+        assert!(matches!(graph.add_edge_with(1, 2, 1.0), Err(GraphError::DuplicateEdge(1, 2))));
+
+        *graph.edge_weight_mut(1, 3).unwrap() += 1.0;
+        let edges: Vec<(usize, f64)> = graph.edges(1).unwrap().map(|(to, weight)| (to, *weight)).collect();
+        assert_eq!(edges, vec![(2, 4.5), (3, 13.0)]);
+
+        graph.remove_edge(1, 2).unwrap();
+        assert_eq!(graph.edge_weight(1, 2), None);
+        graph.remove_node(3).unwrap();
+        assert_eq!(graph.edges(1).unwrap().count(), 0);
+        assert_eq!(graph.edges(2).unwrap().count(), 0);
+    }
+
+    #[test]
+    pub fn undirected_edges() {
+        let mut graph: UnGraph<&str, u32> = UnGraph::default();
+        assert!(!graph.is_directed());
+
+        graph.add_node(Node::with_edges(1, "A", vec![(2, 5)])).unwrap();
+        graph.add_node(Node::with_edges(2, "B", vec![])).unwrap();
+        graph.add_node(Node::with_edges(3, "C", vec![])).unwrap();
+        graph.add_edge_with(3, 2, 8).unwrap();
+
+        // Edge 'a b' is the same edge as 'b a'
+        assert!(matches!(graph.add_edge_with(2, 1, 1), Err(GraphError::DuplicateEdge(2, 1))));
+        assert_eq!(graph.edge_weight(2, 1), Some(&5));
+        *graph.edge_weight_mut(2, 3).unwrap() = 9;
+        assert_eq!(graph.edge_weight(3, 2), Some(&9));
+
+        let mut neighbors: Vec<usize> = graph.neighbors(2).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![1, 3]);
+
+        // Traversal follows edges both ways
+        let bfs: Vec<usize> = graph.bfs(3).map(|(index, _)| index).collect();
+        assert_eq!(bfs, vec![3, 2, 1]);
+
+        graph.remove_edge(2, 1).unwrap();
+        assert_eq!(graph.edge_weight(1, 2), None);
+        assert!(matches!(graph.remove_edge(1, 2), Err(GraphError::MissingEdge(1, 2))));
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![3]);
+
+        graph.remove_node(3).unwrap();
+        assert_eq!(graph.neighbors(2).count(), 0);
+    }
+
+    #[test]
+    pub fn undirected_edges_added_without_payload() {
+        let mut graph: UnGraph<&str> = UnGraph::default();
+
+        graph.add_node(Node::new(1, "A", None)).unwrap();
+        graph.add_node(Node::new(2, "B", None)).unwrap();
+        graph.add_edge(1, 2).unwrap();
+
+        assert!(matches!(graph.add_edge(2, 1), Err(GraphError::DuplicateEdge(2, 1))));
+        assert_eq!(graph.dfs(2).map(|(index, _)| index).collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[test]
+    pub fn multigraph_edges() {
+        let mut graph: Graph<&str, char> = Graph::with_policy(GraphPolicy::multigraph());
+
+        graph.add_node(Node::with_edges(1, "Idle", vec![(2, 'a')])).unwrap();
+        graph.add_node(Node::with_edges(2, "Busy", vec![])).unwrap();
+        let first = graph.get_node(1).unwrap().edges_with_ids().next().unwrap().0;
+
+        // Self-transition and a second transition between the same states
+        let stay = graph.add_edge_with(2, 2, 'w').unwrap();
+        let second = graph.add_edge_with(1, 2, 'b').unwrap();
+        let back = graph.add_edge_with(2, 1, 'd').unwrap();
+        assert_eq!(graph.edge_ids(1, 2), vec![first, second]);
+        assert_eq!(graph.edge(stay), Some((2, 2, &'w')));
+        assert_eq!(graph.in_degree(2), Some(3));
+
+        *graph.edge_mut(second).unwrap().2 = 'c';
+        assert_eq!(graph.edge(second), Some((1, 2, &'c')));
+
+        // Traversal visits each node once
+        assert_eq!(graph.bfs(1).map(|(index, _)| index).collect::<Vec<_>>(), vec![1, 2]);
+
+        // Removing one of the parallel edges keeps the other
+        assert_eq!(graph.remove_edge_by_id(first).unwrap(), 'a');
+        assert!(matches!(graph.remove_edge_by_id(first), Err(GraphError::MissingEdgeId(_))));
+        assert_eq!(graph.edge_ids(1, 2), vec![second]);
+        assert_eq!(graph.predecessors(2), Some(&[2, 1][..]));
+
+        graph.remove_node(1).unwrap();
+        assert_eq!(graph.edge(second), None);
+        assert_eq!(graph.edge(back), None);
+        assert_eq!(graph.edge(stay), Some((2, 2, &'w')));
+    }
+
+    #[test]
+    pub fn default_policy_forbids_loops_and_parallel_edges() {
+        let mut graph = Graph::<&str>::new();
+        assert_eq!(graph.policy(), GraphPolicy::default());
+
+        graph.add_node(Node::new(1, "Text", None)).unwrap();
+        graph.add_node(Node::new(2, "Text", None)).unwrap();
+        let id = graph.add_edge(1, 2).unwrap();
+        assert_eq!(graph.edge(id), Some((1, 2, &())));
+        assert!(matches!(graph.add_edge(1, 1), Err(GraphError::SelfLoop(1))));
+        assert!(matches!(graph.add_edge(1, 2), Err(GraphError::DuplicateEdge(1, 2))));
+    }
+
     #[derive(PartialEq, Debug)]
     struct Dummy {
         head: u32
@@ -236,6 +555,56 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn borrowing_bfs_and_dfs() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(666,"Text", Some(vec![4]))).unwrap();
+        graph.add_node(Node::new(4,"Text", Some(vec![3, 2]))).unwrap();
+        graph.add_node(Node::new(3,"Text", Some(vec![777, 999]))).unwrap();
+        graph.add_node(Node::new(2,"Text", Some(vec![8]))).unwrap();
+        graph.add_node(Node::new(8,"Leaf", Some(vec![111, 222]))).unwrap();
+        graph.add_node(Node::new(999,"Leaf", None)).unwrap();
+        graph.add_node(Node::new(777,"Leaf", None)).unwrap();
+        graph.add_node(Node::new(111,"Leaf", None)).unwrap();
+        graph.add_node(Node::new(222,"Leaf", None)).unwrap();
+
+        let bfs: Vec<usize> = graph.bfs(666).map(|(index, _)| index).collect();
+        assert_eq!(bfs, vec![666, 4, 3, 2, 777, 999, 8, 111, 222]);
+
+        let dfs: Vec<usize> = graph.dfs(666).map(|(index, _)| index).collect();
+        assert_eq!(dfs, vec![666, 4, 3, 777, 999, 2, 8, 111, 222]);
+
+        let leaves: Vec<usize> = graph.dfs(2)
+            .filter(|(_, node)| node.value == "Leaf")
+            .map(|(index, _)| index)
+            .take(2)
+            .collect();
+        assert_eq!(leaves, vec![8, 111]);
+
+        let mut count = 0;
+        for (index, node) in graph.bfs(3) {
+            assert_eq!(index, node.index);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        assert_eq!(graph.bfs(5).count(), 0);
+    }
+
+    #[test]
+    pub fn borrowing_iterators_skip_missing_nodes() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new(1, 1, Some(vec![42, 2]))).unwrap();
+        graph.add_node(Node::new(2, 2, Some(vec![42]))).unwrap();
+
+        assert_eq!(graph.bfs(1).map(|(index, _)| index).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.dfs(1).map(|(index, _)| index).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.post_order(1).map(|(index, _)| index).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(graph.reverse_post_order(1).map(|(index, _)| index).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(graph.bfs_all().count(), 2);
+    }
+
     #[test]
     pub fn check_iterating_loops() -> Result<(), String>{
         let mut graph = Graph::new();
@@ -303,5 +672,805 @@ mod tests {
         }
 
     }
-}
 
+    #[test]
+    pub fn serialize_deserialize_numeric_values() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(1, -15_i64, Some(vec![2]))).unwrap();
+        graph.add_node(Node::new(2, 42_i64, None)).unwrap();
+        graph.set_root(Some(1)).unwrap();
+
+        let path = "./test_resources/serialized_numeric_graph_file".to_string();
+
+        let handler = GraphHandler::new();
+        handler.serialize(&graph, &path).unwrap();
+
+        let mut fresh_graph: Graph<i64> = Graph::new();
+        handler.deserialize(&mut fresh_graph, &path).unwrap();
+
+        assert_eq!(fresh_graph.root, Some(1));
+        assert_eq!(fresh_graph.get_node(1).unwrap().value, -15);
+        assert_eq!(fresh_graph.get_node(2).unwrap().value, 42);
+    }
+
+    #[test]
+    pub fn root_marker_separate_from_value() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(1, "Root of all evil".to_string(), Some(vec![2, 3]))).unwrap();
+        graph.add_node(Node::new(2, "Root".to_string(), None)).unwrap();
+        graph.add_node(Node::new(3, "\\Root".to_string(), None)).unwrap();
+        graph.set_root(Some(1)).unwrap();
+
+        let path = "./test_resources/serialized_labels_graph_file".to_string();
+
+        let handler = GraphHandler::new();
+        handler.serialize(&graph, &path).unwrap();
+
+        let mut fresh_graph: Graph<String> = Graph::new();
+        handler.deserialize(&mut fresh_graph, &path).unwrap();
+
+        assert_eq!(fresh_graph.root, Some(1));
+        assert_eq!(fresh_graph.get_node(1).unwrap().value, "Root of all evil");
+        assert_eq!(fresh_graph.get_node(2).unwrap().value, "Root");
+        assert_eq!(fresh_graph.get_node(3).unwrap().value, "\\Root");
+    }
+
+    #[test]
+    pub fn deserialize_labels_as_values() {
+        let path = "../resources/read_from".to_string();
+
+        let mut graph: Graph<String> = Graph::new();
+        GraphHandler::new().deserialize(&mut graph, &path).unwrap();
+
+        assert_eq!(graph.root, Some(666));
+        assert_eq!(graph.get_node(666).unwrap().value, "");
+        assert_eq!(graph.get_node(4).unwrap().value, "Useless Label");
+    }
+
+    #[test]
+    pub fn tgf_string_round_trip() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(1, 1.5_f64, Some(vec![2, 3]))).unwrap();
+        graph.add_node(Node::new(2, -0.25_f64, Some(vec![3]))).unwrap();
+        graph.add_node(Node::new(3, 8.0_f64, None)).unwrap();
+        graph.set_root(Some(1)).unwrap();
+
+        let handler = GraphHandler::new();
+        let text = handler.to_tgf_string(&graph).unwrap();
+        assert_eq!(text, "1 Root 1.5\n2 -0.25\n3 8\n#\n1 2\n1 3\n2 3\n");
+
+        let mut fresh_graph: Graph<f64> = Graph::new();
+        handler.from_tgf_str(&mut fresh_graph, &text).unwrap();
+        assert_eq!(fresh_graph.root, Some(1));
+        assert_eq!(fresh_graph.get_node(2).unwrap().value, -0.25);
+        assert_eq!(fresh_graph.get_node(2).unwrap().connected(), &vec![3]);
+    }
+
+    #[test]
+    pub fn write_read_tgf_over_buffers() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(7, "Seven".to_string(), Some(vec![8]))).unwrap();
+        graph.add_node(Node::new(8, "Eight".to_string(), None)).unwrap();
+        graph.set_root(Some(7)).unwrap();
+
+        let handler = GraphHandler::new();
+        let mut buffer: Vec<u8> = Vec::new();
+        handler.write_tgf(&graph, &mut buffer).unwrap();
+
+        let mut fresh_graph: Graph<String> = Graph::new();
+        handler.read_tgf(&mut fresh_graph, std::io::Cursor::new(buffer)).unwrap();
+        assert_eq!(fresh_graph.get_node(8).unwrap().value, "Eight");
+        assert_eq!(fresh_graph.get_node(7).unwrap().connected(), &vec![8]);
+    }
+
+    #[test]
+    pub fn serialize_unreachable_nodes() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(5, "Island".to_string(), Some(vec![6]))).unwrap();
+        graph.add_node(Node::new(1, "Root".to_string(), Some(vec![2]))).unwrap();
+        graph.add_node(Node::new(2, "Leaf".to_string(), None)).unwrap();
+        graph.add_node(Node::new(6, "Shore".to_string(), Some(vec![2]))).unwrap();
+        graph.set_root(Some(1)).unwrap();
+
+        let handler = GraphHandler::new();
+        let text = handler.to_tgf_string(&graph).unwrap();
+        assert_eq!(text, "1 Root Root\n2 Leaf\n5 Island\n6 Shore\n#\n1 2\n5 6\n6 2\n");
+
+        let mut fresh_graph: Graph<String> = Graph::new();
+        handler.from_tgf_str(&mut fresh_graph, &text).unwrap();
+        assert_eq!(fresh_graph.arena.len(), 4);
+        assert_eq!(fresh_graph.get_node(6).unwrap().connected(), &vec![2]);
+    }
+
+    #[test]
+    pub fn serialize_rootless_graph() {
+        let mut graph = Graph::new();
+
+        graph.add_node(Node::new(1, 10, Some(vec![2]))).unwrap();
+        graph.add_node(Node::new(2, 20, None)).unwrap();
+
+        let handler = GraphHandler::new();
+        let text = handler.to_tgf_string(&graph).unwrap();
+        assert_eq!(text, "1 10\n2 20\n#\n1 2\n");
+
+        let mut fresh_graph: Graph<i32> = Graph::new();
+        handler.from_tgf_str(&mut fresh_graph, &text).unwrap();
+        assert_eq!(fresh_graph.root, None);
+        assert_eq!(fresh_graph.arena.len(), 2);
+    }
+
+    #[test]
+    pub fn edge_labels_round_trip() {
+        let mut graph: Graph<String, u32> = Graph::default();
+
+        graph.add_node(Node::with_edges(1, "Gateway".to_string(), vec![(2, 40), (3, 7)])).unwrap();
+        graph.add_node(Node::with_edges(2, "Auth".to_string(), vec![(3, 12)])).unwrap();
+        graph.add_node(Node::with_edges(3, "Cache".to_string(), vec![])).unwrap();
+        graph.set_root(Some(1)).unwrap();
+
+        let handler = GraphHandler::new();
+        let text = handler.to_tgf_string(&graph).unwrap();
+        assert_eq!(text, "1 Root Gateway\n2 Auth\n3 Cache\n#\n1 2 40\n1 3 7\n2 3 12\n");
+
+        let mut fresh_graph: Graph<String, u32> = Graph::default();
+        handler.from_tgf_str(&mut fresh_graph, &text).unwrap();
+        assert_eq!(fresh_graph.edge_weight(1, 2), Some(&40));
+        assert_eq!(fresh_graph.edge_weight(2, 3), Some(&12));
+
+        let result = handler.from_tgf_str(&mut Graph::<String, u32>::default(), "1\n2\n#\n1 2 far\n");
+        assert!(matches!(result, Err(GraphError::Parse { line: 4, column: 5, .. })));
+    }
+
+    #[test]
+    pub fn deserialize_edge_labels() {
+        let path = "../resources/read_from".to_string();
+
+        let mut graph: Graph<String, String> = Graph::default();
+        GraphHandler::new().deserialize(&mut graph, &path).unwrap();
+
+        assert_eq!(graph.edge_weight(666, 4).map(String::as_str), Some("Useless Label"));
+        assert_eq!(graph.edge_weight(8, 222).map(String::as_str), Some("Useless Label"));
+    }
+
+    #[test]
+    pub fn undirected_tgf_round_trip() {
+        let handler = GraphHandler::new();
+        let text = "1 Root A\n2 B\n3 C\n#\n2 1 4\n2 3 6\n";
+
+        let mut graph: UnGraph<String, u32> = UnGraph::default();
+        handler.from_tgf_str(&mut graph, text).unwrap();
+        assert_eq!(graph.edge_weight(1, 2), Some(&4));
+        assert_eq!(handler.to_tgf_string(&graph).unwrap(), text);
+
+        // The same edge written in both directions is a duplicate
+        let mut graph: UnGraph<String, u32> = UnGraph::default();
+        let result = handler.from_tgf_str(&mut graph, "1\n2\n#\n1 2 4\n2 1 4\n");
+        assert!(matches!(result, Err(GraphError::Parse { line: 5, .. })));
+    }
+
+    #[test]
+    pub fn multigraph_tgf_round_trip() {
+        let handler = GraphHandler::new();
+        let text = "1 Root Idle\n2 Busy\n#\n1 2 go\n1 2 rush\n2 2 wait\n";
+
+        let mut graph: Graph<String, String> = Graph::with_policy(GraphPolicy::multigraph());
+        handler.from_tgf_str(&mut graph, text).unwrap();
+        assert_eq!(graph.edge_ids(1, 2).len(), 2);
+        assert_eq!(handler.to_tgf_string(&graph).unwrap(), text);
+
+        // The same file is rejected by a graph with the default policy
+        let mut graph: Graph<String, String> = Graph::default();
+        let result = handler.from_tgf_str(&mut graph, text);
+        assert!(matches!(result, Err(GraphError::Parse { line: 5, .. })));
+    }
+
+    #[test]
+    pub fn deserialize_missing_file() {
+        let path = "./test_resources/no_such_file".to_string();
+
+        let mut graph: Graph<String> = Graph::new();
+        let result = GraphHandler::new().deserialize(&mut graph, &path);
+        assert!(matches!(result, Err(GraphError::Io(_))));
+    }
+
+    #[test]
+    pub fn serialize_edge_to_missing_node() {
+        let mut graph = Graph::new();
+        graph.add_node(Node::new(1, 10, Some(vec![42]))).unwrap();
+        graph.set_root(Some(1)).unwrap();
+        let mut output = Vec::new();
+        assert!(matches!(GraphHandler::new().write_tgf(&graph, &mut output), Err(GraphError::MissingNode(42))));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    pub fn deserialize_unlabeled_nodes() {
+        let text = "1 Root\n2\n#\n1 2\n";
+        let handler = GraphHandler::new();
+        // Empty labels are decoded like any other, so they only load into types that accept them
+        let mut graph: Graph<String> = Graph::new();
+        handler.from_tgf_str(&mut graph, text).unwrap();
+        assert_eq!(graph.get_node(2).unwrap().value, "");
+        assert_eq!(graph.root, Some(1));
+
+        let mut graph: Graph<i32> = Graph::new();
+        match handler.from_tgf_str(&mut graph, text) {
+            Err(GraphError::Parse { line, reason, .. }) => {
+                assert_eq!(line, 1);
+                assert!(reason.contains("Invalid Value of Node 1"));
+            }
+            other => panic!("Expected a Parse Error, Got {:?}", other),
+        }
+    }
+
+    // Function deserializes a malformed file and returns the position and the reason of an error
+    fn malformed<T: ValueCodec>(name: &str) -> (usize, usize, String) {
+        let path = format!("./test_resources/malformed/{}.tgf", name);
+        let mut graph: Graph<T> = Graph::new();
+        match GraphHandler::new().deserialize(&mut graph, &path) {
+            Err(GraphError::Parse { line, column, reason }) => (line, column, reason),
+            other => panic!("Expected a Parse Error for {}, Got {:?}", name, other),
+        }
+    }
+
+    #[test]
+    pub fn deserialize_malformed_files() {
+        let cases = [
+            ("bad_index", 2, 1, "`x2`"),
+            ("negative_index", 2, 1, "`-2`"),
+            ("edge_one_field", 5, 2, "`2`"),
+            ("edge_bad_index", 4, 3, "`two`"),
+            ("edge_unknown_node", 5, 3, "`2 3 Label`: Node 3"),
+            ("edge_self_loop", 4, 1, "`2 2`: Can't Form an Edge From the Node 2 to Itself"),
+            ("duplicate_edge", 5, 1, "`1 2`: Multiple Edges From Node 1 to Node 2"),
+            ("duplicate_node", 3, 1, "`2`: Node 2 is Already"),
+            ("duplicate_root", 2, 3, "`2 Root Value`"),
+            ("non_utf8", 2, 6, "Lab"),
+        ];
+        for (name, line, column, text) in cases {
+            let (found_line, found_column, reason) = malformed::<String>(name);
+            assert_eq!((found_line, found_column), (line, column), "Wrong Position for {}", name);
+            assert!(reason.contains(text), "Reason of {} is `{}`", name, reason);
+        }
+    }
+
+    #[test]
+    pub fn deserialize_malformed_value() {
+        let (line, column, reason) = malformed::<i32>("bad_value");
+        assert_eq!((line, column), (2, 3));
+        assert!(reason.contains("Node 2"));
+    }
+
+    // Tests for Ordering
+
+    // Function builds a directed graph with unit values out of (from, to) pairs
+    fn graph_of(nodes: usize, edges: &[(usize, usize)]) -> Graph<()> {
+        let mut graph = Graph::new();
+        for index in 0..nodes {
+            graph.add_node(Node::new(index, (), None)).unwrap();
+        }
+        for &(from, to) in edges {
+            graph.add_edge(from, to).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    pub fn toposort_orders_every_edge() {
+        let edges = [(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)];
+        let graph = graph_of(6, &edges);
+        let order = graph.toposort().unwrap();
+        assert_eq!(order.len(), 6);
+        let position = |index| order.iter().position(|&other| other == index).unwrap();
+        for (from, to) in edges {
+            assert!(position(from) < position(to), "{} Must Go Before {}", from, to);
+        }
+        assert!(graph.is_acyclic());
+    }
+
+    #[test]
+    pub fn toposort_returns_cycle() {
+        let graph = graph_of(5, &[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        match graph.toposort() {
+            Err(GraphError::Cycle(cycle)) => assert_eq!(cycle, vec![1, 2, 3]),
+            other => panic!("Expected a Cycle, Got {:?}", other),
+        }
+        assert!(!graph.is_acyclic());
+        assert!(matches!(graph.toposort_layers(), Err(GraphError::Cycle(_))));
+
+        // Self-loop is a cycle of a single node
+        let mut graph: Graph<()> = Graph::with_policy(GraphPolicy::multigraph());
+        graph.add_node(Node::new(0, (), None)).unwrap();
+        graph.add_edge(0, 0).unwrap();
+        assert!(matches!(graph.toposort(), Err(GraphError::Cycle(cycle)) if cycle == vec![0]));
+    }
+
+    #[test]
+    pub fn toposort_layers_for_scheduling() {
+        let graph = graph_of(6, &[(0, 2), (1, 2), (2, 3), (2, 4), (1, 4), (3, 5), (4, 5)]);
+        let layers = graph.toposort_layers().unwrap();
+        assert_eq!(layers, vec![vec![0, 1], vec![2], vec![3, 4], vec![5]]);
+        assert_eq!(graph_of(0, &[]).toposort_layers().unwrap(), Vec::<Vec<usize>>::new());
+    }
+
+    // Tests for Paths
+
+    // Function builds a directed graph with weighted edges out of (from, to, weight) triples
+    fn weighted_graph<W>(nodes: usize, edges: Vec<(usize, usize, W)>) -> Graph<(), W> {
+        let mut graph = Graph::default();
+        for index in 0..nodes {
+            graph.add_node(Node::with_edges(index, (), Vec::new())).unwrap();
+        }
+        for (from, to, weight) in edges {
+            graph.add_edge_with(from, to, weight).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    pub fn shortest_path_by_hops() {
+        let graph = graph_of(6, &[(0, 1), (1, 2), (2, 3), (0, 4), (4, 3)]);
+        assert_eq!(graph.shortest_path(0, 3), Some(vec![0, 4, 3]));
+        assert_eq!(graph.shortest_path(2, 2), Some(vec![2]));
+        assert_eq!(graph.shortest_path(3, 0), None);
+        assert_eq!(graph.shortest_path(0, 5), None);
+        assert_eq!(graph.shortest_path(0, 42), None);
+
+        // Undirected graph has paths both ways
+        let mut graph: UnGraph<()> = UnGraph::default();
+        for index in 0..3 {
+            graph.add_node(Node::with_edges(index, (), Vec::new())).unwrap();
+        }
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        assert_eq!(graph.shortest_path(2, 0), Some(vec![2, 1, 0]));
+    }
+
+    #[test]
+    pub fn dijkstra_distances_and_paths() {
+        let graph = weighted_graph(6, vec![(0, 1, 7), (0, 2, 9), (0, 5, 14), (1, 2, 10), (1, 3, 15),
+            (2, 3, 11), (2, 5, 2), (3, 4, 6), (5, 4, 9)]);
+        let paths = graph.dijkstra(0).unwrap();
+        assert_eq!(paths.source, 0);
+        assert_eq!(paths.distance(4), Some(20));
+        assert_eq!(paths.distance(3), Some(20));
+        assert_eq!(paths.distance(0), Some(0));
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 5, 4]));
+        assert_eq!(paths.path_to(0), Some(vec![0]));
+        assert_eq!(paths.predecessors.get(&5), Some(&2));
+
+        // Nothing is reachable from the last node
+        let paths = graph.dijkstra(4).unwrap();
+        assert_eq!(paths.distance(0), None);
+        assert_eq!(paths.path_to(0), None);
+        assert!(matches!(graph.dijkstra(42), Err(GraphError::MissingNode(42))));
+    }
+
+    #[test]
+    pub fn dijkstra_float_weights() {
+        let graph = weighted_graph(3, vec![(0, 1, 0.5), (1, 2, 0.25), (0, 2, 1.0)]);
+        let paths = graph.dijkstra(0).unwrap();
+        assert_eq!(paths.distance(2), Some(0.75));
+        assert_eq!(paths.path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    pub fn dijkstra_negative_weight() {
+        let graph = weighted_graph(2, vec![(0, 1, 1), (1, 0, -5)]);
+        assert!(matches!(graph.dijkstra(0), Err(GraphError::NegativeWeight(1, 0))));
+        assert!(matches!(graph.astar(0, 1, |_, _, &weight| weight, |_, _| 0), Ok(Some(_))));
+        assert!(matches!(graph.astar(1, 0, |_, _, &weight| weight, |_, _| 0), Err(GraphError::NegativeWeight(1, 0))));
+    }
+
+    // Function builds an undirected grid graph of the given size. Values of nodes are their coordinates
+    // Nodes of 'walls' are left out of the graph
+    fn grid(width: usize, height: usize, walls: &[(usize, usize)]) -> UnGraph<(usize, usize), u32> {
+        let mut graph = UnGraph::default();
+        let index = |x: usize, y: usize| y * width + x;
+        for y in 0..height {
+            for x in 0..width {
+                if !walls.contains(&(x, y)) {
+                    graph.add_node(Node::with_edges(index(x, y), (x, y), Vec::new())).unwrap();
+                }
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                if x + 1 < width && graph.in_graph(index(x, y)) && graph.in_graph(index(x + 1, y)) {
+                    graph.add_edge_with(index(x, y), index(x + 1, y), 1).unwrap();
+                }
+                if y + 1 < height && graph.in_graph(index(x, y)) && graph.in_graph(index(x, y + 1)) {
+                    graph.add_edge_with(index(x, y), index(x, y + 1), 1).unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    pub fn astar_on_grid() {
+        let graph = grid(10, 10, &[]);
+        let goal = (9, 9);
+        let manhattan = |_, &(x, y): &(usize, usize)| (goal.0 - x + goal.1 - y) as u32;
+        let (path, cost) = graph.astar(0, 99, |_, _, &weight| weight, manhattan).unwrap().unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!((path[0], path[18]), (0, 99));
+        // Each step of the path goes to a neighboring cell
+        for step in path.windows(2) {
+            assert!(graph.neighbors(step[0]).any(|next| next == step[1]));
+        }
+    }
+
+    #[test]
+    pub fn astar_around_walls() {
+        // Wall across the grid with a gap at the bottom
+        let walls: Vec<(usize, usize)> = (0..7).map(|y| (4, y)).collect();
+        let graph = grid(8, 8, &walls);
+        let goal = (7, 0);
+        let manhattan = |_, &(x, y): &(usize, usize)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32;
+        let (path, cost) = graph.astar(0, 7, |_, _, &weight| weight, manhattan).unwrap().unwrap();
+        assert_eq!(cost, graph.dijkstra(0).unwrap().distance(7).unwrap());
+        assert_eq!(cost, 21);
+        assert!(path.contains(&(7 * 8 + 4)));
+
+        // Goal cut off by a full wall can not be reached
+        let walls: Vec<(usize, usize)> = (0..8).map(|y| (4, y)).collect();
+        let graph = grid(8, 8, &walls);
+        assert_eq!(graph.astar(0, 7, |_, _, &weight| weight, |_, _| 0).unwrap(), None);
+        assert!(matches!(graph.astar(0, 4, |_, _, &weight| weight, |_, _| 0), Err(GraphError::MissingNode(4))));
+    }
+
+    #[test]
+    pub fn astar_inconsistent_heuristic() {
+        // Heuristic never overestimates, but drops by more than the cost of the edge 0 -> 1
+        let graph = weighted_graph(4, vec![(0, 1, 1), (1, 2, 1), (0, 2, 3), (2, 3, 10)]);
+        let estimates = [7, 6, 0, 0];
+        let (path, cost) = graph.astar(0, 3, |_, _, &weight| weight, |index, _| estimates[index]).unwrap().unwrap();
+        assert_eq!(cost, graph.dijkstra(0).unwrap().distance(3).unwrap());
+        assert_eq!((path, cost), (vec![0, 1, 2, 3], 12));
+    }
+
+    #[test]
+    pub fn bellman_ford_negative_edges() {
+        let graph = weighted_graph(5, vec![(0, 1, 6), (0, 2, 7), (1, 2, 8), (1, 3, 5), (1, 4, -4),
+            (2, 3, -3), (2, 4, 9), (3, 1, -2), (4, 3, 7)]);
+        let paths = graph.bellman_ford(0).unwrap();
+        assert_eq!(paths.distance(1), Some(2));
+        assert_eq!(paths.distance(3), Some(4));
+        assert_eq!(paths.distance(4), Some(-2));
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 3, 1, 4]));
+        // Without negative edges it agrees with Dijkstra
+        let graph = weighted_graph(4, vec![(0, 1, 1), (1, 2, 2), (0, 2, 4), (2, 3, 1)]);
+        assert_eq!(graph.bellman_ford(0).unwrap().distances, graph.dijkstra(0).unwrap().distances);
+        assert!(matches!(graph.bellman_ford(42), Err(GraphError::MissingNode(42))));
+    }
+
+    #[test]
+    pub fn bellman_ford_negative_cycle() {
+        let graph = weighted_graph(5, vec![(0, 1, 1), (1, 2, 1), (2, 3, -2), (3, 1, -1), (3, 4, 1)]);
+        match graph.bellman_ford(0) {
+            Err(GraphError::NegativeCycle(mut cycle)) => {
+                // Cycle may start with any of its nodes
+                let first = cycle.iter().position(|&node| node == 1).unwrap();
+                cycle.rotate_left(first);
+                assert_eq!(cycle, vec![1, 2, 3]);
+            }
+            other => panic!("Expected a Negative Cycle, Got {:?}", other.map(|paths| paths.distances)),
+        }
+        // Negative cycle that can not be reached from the source does not matter
+        assert_eq!(graph.bellman_ford(4).unwrap().distance(4), Some(0));
+    }
+
+    #[test]
+    pub fn floyd_warshall_keyed_by_index() {
+        let mut graph: Graph<(), i32> = Graph::default();
+        for index in [40, 10, 30, 20, 50] {
+            graph.add_node(Node::with_edges(index, (), Vec::new())).unwrap();
+        }
+        for (from, to, weight) in [(10, 20, 3), (20, 30, -1), (10, 30, 5), (30, 40, 2), (40, 10, 1), (50, 10, 1)] {
+            graph.add_edge_with(from, to, weight).unwrap();
+        }
+        // Removing a node moves the last one of the arena to its place
+        graph.remove_node(50).unwrap();
+        graph.remove_node(40).unwrap();
+        graph.add_node(Node::with_edges(40, (), vec![(10, 1)])).unwrap();
+        graph.add_edge_with(30, 40, 2).unwrap();
+
+        let matrix = graph.floyd_warshall().unwrap();
+        assert_eq!(matrix.nodes().len(), 4);
+        assert_eq!(matrix.distance(10, 30), Some(2));
+        assert_eq!(matrix.path(10, 40), Some(vec![10, 20, 30, 40]));
+        assert_eq!(matrix.distance(40, 30), Some(3));
+        assert_eq!(matrix.path(20, 20), Some(vec![20]));
+        assert_eq!(matrix.distance(20, 50), None);
+        assert_eq!(matrix.path(50, 20), None);
+        // Every distance agrees with Bellman-Ford
+        for &from in matrix.nodes() {
+            let paths = graph.bellman_ford(from).unwrap();
+            for &to in matrix.nodes() {
+                assert_eq!(matrix.distance(from, to), paths.distance(to), "Distance From {} to {}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    pub fn floyd_warshall_negative_cycle() {
+        let graph = weighted_graph(4, vec![(0, 1, 1), (1, 2, -3), (2, 1, 1), (2, 3, 1)]);
+        match graph.floyd_warshall() {
+            Err(GraphError::NegativeCycle(mut cycle)) => {
+                cycle.sort();
+                assert_eq!(cycle, vec![1, 2]);
+            }
+            other => panic!("Expected a Negative Cycle, Got {:?}", other),
+        }
+        // Rounding of floats makes this cycle slightly negative
+        let graph = weighted_graph(3, vec![(0, 1, 0.3), (1, 2, -0.1), (2, 0, -0.2)]);
+        match graph.floyd_warshall() {
+            Err(GraphError::NegativeCycle(mut cycle)) => {
+                cycle.sort();
+                assert_eq!(cycle, vec![0, 1, 2]);
+            }
+            other => panic!("Expected a Negative Cycle, Got {:?}", other),
+        }
+        let unreachable = weighted_graph(3, vec![(0, 1, 2)]).floyd_warshall().unwrap();
+        assert_eq!(unreachable.distance(1, 0), None);
+        assert_eq!(unreachable.distance(0, 1), Some(2));
+    }
+
+    #[test]
+    pub fn floyd_warshall_dense_negative_graph() {
+        // Relaxing on after the first negative cycle would overflow these distances
+        let edges = (0..35).flat_map(|from| (0..35).filter(move |&to| to != from).map(move |to| (from, to, -1i32))).collect();
+        match weighted_graph(35, edges).floyd_warshall() {
+            Err(GraphError::NegativeCycle(cycle)) => assert!(cycle.len() >= 2),
+            other => panic!("Expected a Negative Cycle, Got {:?}", other),
+        }
+    }
+
+    // Tests for Components
+
+    #[test]
+    pub fn strongly_connected_components() {
+        let mut graph = graph_of(8, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)]);
+        // Edge to a node that is not in the graph is skipped
+        graph.add_node(Node::new(8, (), Some(vec![42, 6]))).unwrap();
+        let components = graph.strongly_connected_components();
+        assert_eq!(components, vec![vec![8], vec![6, 7], vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(graph_of(3, &[]).strongly_connected_components().len(), 3);
+    }
+
+    #[test]
+    pub fn condensation_is_acyclic() {
+        let mut graph = graph_of(8, &[(0, 1), (1, 2), (2, 0), (2, 3), (1, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)]);
+        graph.set_root(Some(4)).unwrap();
+        assert!(!graph.is_acyclic());
+        let condensed = graph.condensation();
+        assert_eq!(condensed.len(), 3);
+        assert_eq!(condensed.get_node(1).unwrap().value, vec![0, 1, 2]);
+        // Two edges from the first component to the second one become a single edge
+        assert_eq!(condensed.successors(1), Some(&[2][..]));
+        assert_eq!(condensed.successors(0), Some(&[2][..]));
+        assert_eq!(condensed.root, Some(2));
+        assert_eq!(condensed.toposort().unwrap(), vec![1, 0, 2]);
+        assert!(condensed.is_acyclic());
+    }
+
+    #[test]
+    pub fn connected_components_ignore_direction() {
+        // Node 2 can't be reached from 0 or 1 but shares an edge with them
+        let graph = graph_of(7, &[(0, 1), (2, 1), (3, 4), (5, 4)]);
+        assert_eq!(graph.connected_components(), vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+        let mut graph: UnGraph<()> = UnGraph::default();
+        for index in 0..4 {
+            graph.add_node(Node::with_edges(index, (), Vec::new())).unwrap();
+        }
+        graph.add_edge(3, 1).unwrap();
+        assert_eq!(graph.connected_components(), vec![vec![0], vec![1, 3], vec![2]]);
+    }
+
+    #[test]
+    pub fn rootless_traversal_covers_arena() {
+        let graph = graph_of(6, &[(0, 1), (1, 2), (3, 4), (5, 3)]);
+        let bfs: Vec<(usize, usize)> = graph.bfs_all().map(|(component, index, _)| (component, index)).collect();
+        // Node 5 starts a tree of its own but shares a component with 3 and 4
+        assert_eq!(bfs, vec![(0, 0), (0, 1), (0, 2), (1, 3), (1, 4), (1, 5)]);
+        let dfs: Vec<(usize, usize)> = graph.dfs_all().map(|(component, index, _)| (component, index)).collect();
+        assert_eq!(dfs, bfs);
+
+        // Graph without a root is iterated as a whole
+        let mut iterator = graph.iterator();
+        let mut visited = Vec::new();
+        while let Some(index) = iterator.next_depth_search(&graph) {
+            visited.push(index);
+        }
+        assert_eq!(visited, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(iterator.tree(), 2);
+
+        // Rooted iterator stays within the reach of the root
+        let mut iterator = GraphIter::new(Some(3)).unwrap();
+        let mut visited = Vec::new();
+        while let Some(index) = iterator.next_breadth_search(&graph) {
+            visited.push(index);
+        }
+        assert_eq!(visited, vec![3, 4]);
+        assert_eq!(iterator.tree(), 0);
+        assert_eq!(graph_of(0, &[]).bfs_all().count(), 0);
+    }
+
+    #[test]
+    pub fn rootless_traversal_yields_components() {
+        // Node 1 can't be reached from 0, so it starts a new tree of the same component
+        let graph = graph_of(2, &[(1, 0)]);
+        let bfs: Vec<(usize, usize)> = graph.bfs_all().map(|(component, index, _)| (component, index)).collect();
+        assert_eq!(bfs, vec![(0, 0), (0, 1)]);
+        assert_eq!(graph.connected_components(), vec![vec![0, 1]]);
+
+        let mut iterator = graph.iterator();
+        let mut trees = Vec::new();
+        while let Some(index) = iterator.next_breadth_search(&graph) {
+            trees.push((iterator.tree(), index));
+        }
+        assert_eq!(trees, vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    pub fn rootless_traversal_after_removal() {
+        let mut graph = graph_of(4, &[]);
+        let mut iterator = GraphIter::rootless();
+        let mut visited = vec![
+            iterator.next_breadth_search(&graph).unwrap(),
+            iterator.next_breadth_search(&graph).unwrap(),
+        ];
+        // Last node of the arena takes the place of the removed one, before the saved position
+        graph.remove_node(0).unwrap();
+        while let Some(index) = iterator.next_breadth_search(&graph) {
+            visited.push(index);
+        }
+        assert_eq!(visited, vec![0, 1, 3, 2]);
+    }
+
+    // Tests for Visitor
+
+    #[test]
+    pub fn dfs_visitor_events() {
+        use DfsEvent::*;
+        let graph = graph_of(4, &[(0, 1), (0, 2), (1, 2), (2, 0), (3, 2)]);
+        let mut events = Vec::new();
+        let control = graph.depth_first_search(graph.nodes().map(|node| node.index), |event| {
+            events.push(event);
+            Control::Continue
+        });
+        assert_eq!(control, Control::Continue);
+        assert_eq!(events, vec![
+            Discover(0, 0), TreeEdge(0, 1), Discover(1, 1), TreeEdge(1, 2), Discover(2, 2),
+            BackEdge(2, 0), Finish(2, 3), Finish(1, 4), ForwardOrCrossEdge(0, 2), Finish(0, 5),
+            Discover(3, 6), ForwardOrCrossEdge(3, 2), Finish(3, 7),
+        ]);
+    }
+
+    #[test]
+    pub fn dfs_visitor_undirected() {
+        use DfsEvent::*;
+        let mut graph: UnGraph<()> = UnGraph::with_policy(GraphPolicy::multigraph());
+        for index in 0..4 {
+            graph.add_node(Node::with_edges(index, (), Vec::new())).unwrap();
+        }
+        // Path 0 - 1 - 2 has no cycle, 2 = 3 is a cycle of two parallel edges
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(2, 3).unwrap();
+        graph.add_edge(2, 3).unwrap();
+        let mut back_edges = Vec::new();
+        graph.depth_first_search([0], |event| {
+            if let BackEdge(from, to) = event {
+                back_edges.push((from, to));
+            }
+            Control::Continue
+        });
+        assert_eq!(back_edges, vec![(3, 2)]);
+    }
+
+    #[test]
+    pub fn dfs_visitor_prune_and_break() {
+        use DfsEvent::*;
+        let graph = graph_of(5, &[(0, 1), (1, 2), (0, 3), (3, 4)]);
+        // Subtree of node 1 is skipped
+        let mut discovered = Vec::new();
+        graph.depth_first_search([0], |event| match event {
+            Discover(node, _) => {
+                discovered.push(node);
+                if node == 1 { Control::Prune } else { Control::Continue }
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(discovered, vec![0, 1, 3, 4]);
+
+        // Edge to node 3 is not followed, but node 3 is still found from the next start
+        let mut discovered = Vec::new();
+        graph.depth_first_search([0, 3], |event| match event {
+            Discover(node, _) => {
+                discovered.push(node);
+                Control::Continue
+            }
+            TreeEdge(_, 3) => Control::Prune,
+            _ => Control::Continue,
+        });
+        assert_eq!(discovered, vec![0, 1, 2, 3, 4]);
+
+        // Search stops at the first node without edges
+        let mut finished = Vec::new();
+        let control = graph.depth_first_search([0], |event| match event {
+            Finish(node, time) => {
+                finished.push((node, time));
+                Control::Break
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(control, Control::Break);
+        assert_eq!(finished, vec![(2, 3)]);
+    }
+
+    #[test]
+    pub fn bfs_with_depths() {
+        let graph = graph_of(7, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        let visited: Vec<(usize, usize, Option<usize>)> = graph.bfs_depths(0, None).collect();
+        assert_eq!(visited, vec![(0, 0, None), (1, 1, Some(0)), (2, 1, Some(0)), (3, 2, Some(1)),
+            (4, 3, Some(3)), (5, 4, Some(4))]);
+
+        // Blast radius within two hops
+        let near: Vec<usize> = graph.bfs_depths(1, Some(2)).map(|(index, _, _)| index).collect();
+        assert_eq!(near, vec![1, 3, 4]);
+        assert_eq!(graph.bfs_depths(0, Some(0)).count(), 1);
+        assert_eq!(graph.bfs_depths(42, None).count(), 0);
+    }
+
+    #[test]
+    pub fn bfs_levels() {
+        let graph = graph_of(7, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        assert_eq!(graph.levels(0), vec![vec![0], vec![1, 2], vec![3], vec![4], vec![5]]);
+        assert_eq!(graph.levels(6), vec![vec![6]]);
+        assert!(graph.levels(42).is_empty());
+    }
+
+    #[test]
+    pub fn post_order_traversals() {
+        let graph = graph_of(6, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+        let post: Vec<usize> = graph.post_order(0).map(|(index, _)| index).collect();
+        assert_eq!(post, vec![4, 3, 1, 2, 0]);
+        let reverse: Vec<usize> = graph.reverse_post_order(0).map(|(index, _)| index).collect();
+        assert_eq!(reverse, vec![0, 2, 1, 3, 4]);
+        // Reverse post-order of a graph without cycles is a topological order
+        let mut whole = GraphIter::rootless();
+        let mut order = Vec::new();
+        while let Some(index) = whole.next_reverse_post_order(&graph) {
+            order.push(index);
+        }
+        assert_eq!(order, graph.toposort().unwrap());
+        assert_eq!(graph.post_order(42).count(), 0);
+    }
+
+    #[test]
+    pub fn post_order_with_cycles() {
+        // Edges back to nodes in progress are not followed, so the order is the same on every run
+        let graph = graph_of(4, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 1)]);
+        for _ in 0..3 {
+            let post: Vec<usize> = graph.post_order(0).map(|(index, _)| index).collect();
+            assert_eq!(post, vec![3, 2, 1, 0]);
+        }
+        let mut iterator = GraphIter::new(Some(1)).unwrap();
+        let mut post = Vec::new();
+        while let Some(index) = iterator.next_post_order(&graph) {
+            post.push(index);
+        }
+        assert_eq!(post, vec![0, 3, 2, 1]);
+        iterator.reset(Some(1));
+        assert_eq!(iterator.next_reverse_post_order(&graph), Some(1));
+    }
+}
@@ -3,27 +3,46 @@ use std::fmt;
 
 // Module of a graph node
 
+// Index of a node. Either picked by the user or given out by graph.insert()
+pub type NodeId = usize;
+
+// Identifier of an edge. Given out by the graph when the edge is added and never reused
+pub type EdgeId = usize;
+
 // Struct of a graph node
-pub struct Node<T> {
+// E is the type of a payload (e.g. weight) of edges going out of the node
+pub struct Node<T, E = ()> {
     // Node has an index, a value and may have other nodes connected to it.
     // Index of a node is NOT the same as node's position in the arena
     pub index: usize,
     pub value: T,
-    // Connected nodes are accessed through their arena indexes
+    // Connected nodes are accessed through their indexes
     // Nodes deleted from the graph are deleted from this vector as well
     // Connected nodes should be added via graph.add_node()
-    connected: Vec<usize>
+    connected: Vec<usize>,
+    // Payloads of edges. Payload of the edge to connected[i] is weights[i]
+    weights: Vec<E>,
+    // Identifiers of edges. Identifier of the edge to connected[i] is ids[i]
+    // They are given out when the node is added to the graph
+    ids: Vec<EdgeId>,
 }
 
 impl<T> Node<T> {
     // Constructor for a new node
     // If connected nodes are mentioned here - they are added to the node
     pub fn new(index: usize, value: T, connected: Option<Vec<usize>>) -> Self {
-        if connected.is_none() {
-            Node{index, value, connected: Vec::new()}
-        } else {
-            Node{index, value, connected: connected.unwrap()}
-        }
+        let connected = connected.unwrap_or_default();
+        let weights = vec![(); connected.len()];
+        Node{index, value, connected, weights, ids: Vec::new()}
+    }
+}
+
+impl<T, E> Node<T, E> {
+    // Constructor for a new node with edges carrying payloads
+    // Each edge is a pair of an index of connected node and a payload
+    pub fn with_edges(index: usize, value: T, edges: Vec<(usize, E)>) -> Self {
+        let (connected, weights) = edges.into_iter().unzip();
+        Node{index, value, connected, weights, ids: Vec::new()}
     }
 
     // Getter for 'connected'
@@ -31,9 +50,76 @@ impl<T> Node<T> {
         &self.connected
     }
 
-    // Getter for mutable 'connected'
-    pub fn connected_mut(&mut self) -> &mut Vec<usize> {
-        &mut self.connected
+    // Function returns an iterator over edges going out of the node as (to, payload) pairs
+    pub fn edges(&self) -> impl Iterator<Item = (usize, &E)> {
+        self.connected.iter().copied().zip(self.weights.iter())
+    }
+
+    // Function returns an iterator over edges going out of the node as (id, to, payload) triples
+    // Edges of a node that is not in a graph have no identifiers
+    pub fn edges_with_ids(&self) -> impl Iterator<Item = (EdgeId, usize, &E)> {
+        self.ids.iter().copied().zip(self.edges()).map(|(id, (to, weight))| (id, to, weight))
+    }
+
+    // Function returns an iterator over edges going out of the node with mutable payloads
+    pub fn edges_mut(&mut self) -> impl Iterator<Item = (usize, &mut E)> {
+        self.connected.iter().copied().zip(self.weights.iter_mut())
+    }
+
+    // Edges are only changed by the graph, so that its reverse index stays in sync
+
+    // Function gives out identifiers to all edges of the node starting with 'first'
+    pub(crate) fn assign_edge_ids(&mut self, first: EdgeId) {
+        self.ids = (first..first + self.connected.len()).collect();
+    }
+
+    // Function adds an edge to the node
+    pub(crate) fn push_edge(&mut self, id: EdgeId, to: usize, weight: E) {
+        self.connected.push(to);
+        self.weights.push(weight);
+        self.ids.push(id);
+    }
+
+    // Function removes all edges to the given node and returns their identifiers
+    pub(crate) fn remove_edges_to(&mut self, to: usize) -> Vec<EdgeId> {
+        let mut removed = Vec::new();
+        let mut i = 0;
+        while i < self.connected.len() {
+            if self.connected[i] == to {
+                removed.push(self.ids[i]);
+                self.remove_edge_at(i);
+            } else {
+                i += 1;
+            }
+        }
+        removed
+    }
+
+    // Function removes the edge with the given identifier and returns its end and payload
+    pub(crate) fn remove_edge_id(&mut self, id: EdgeId) -> Option<(usize, E)> {
+        let i = self.edge_position(id)?;
+        Some(self.remove_edge_at(i))
+    }
+
+    // Function removes the edge at the given position
+    fn remove_edge_at(&mut self, i: usize) -> (usize, E) {
+        self.ids.remove(i);
+        (self.connected.remove(i), self.weights.remove(i))
+    }
+
+    // Function finds the position of the edge with the given identifier
+    pub(crate) fn edge_position(&self, id: EdgeId) -> Option<usize> {
+        self.ids.iter().position(|&other| other == id)
+    }
+
+    // Function returns the end and the payload of the edge at the given position
+    pub(crate) fn edge_at(&self, i: usize) -> (usize, &E) {
+        (self.connected[i], &self.weights[i])
+    }
+
+    // Function returns the end and the mutable payload of the edge at the given position
+    pub(crate) fn edge_at_mut(&mut self, i: usize) -> (usize, &mut E) {
+        (self.connected[i], &mut self.weights[i])
     }
 
     // Function changes the value of a node
@@ -44,7 +130,7 @@ impl<T> Node<T> {
 
 
 // Implementation of traits for propper output
-impl<T: Display + Debug> fmt::Display for Node<T> {
+impl<T: Display + Debug, E> fmt::Display for Node<T, E> {
     
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\nNode {}\n\tValue: {}\n\tChild nodes: {:?}", self.index, self.value, self.connected)
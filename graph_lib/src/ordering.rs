@@ -0,0 +1,104 @@
+use super::graph::{Graph, Directed};
+use super::error::GraphError;
+use std::collections::HashMap;
+
+// Module of topological ordering of directed graphs
+
+// State of a node during the depth-first-search
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    // Node is on the stack of the search
+    Open,
+    // Node and all nodes reachable from it are processed
+    Done,
+}
+
+impl<T, E> Graph<T, E, Directed> {
+
+    // Function returns indexes of all nodes so that each edge goes from an earlier node to a later one
+    // If there is no such order, the cycle found is returned as 'GraphError::Cycle'
+    pub fn toposort(&self) -> Result<Vec<usize>, GraphError> {
+        let mut marks: HashMap<usize, Mark> = HashMap::with_capacity(self.len());
+        // Nodes in the order they are done with (post-order)
+        let mut order = Vec::with_capacity(self.len());
+        for start in self.nodes().map(|node| node.index) {
+            if marks.contains_key(&start) {
+                continue;
+            }
+            marks.insert(start, Mark::Open);
+            // Stack holds nodes of the current path and positions of their next edges
+            let mut stack = vec![(start, 0)];
+            while let Some(&(node, next)) = stack.last() {
+                let successors = self.successors(node).unwrap_or(&[]);
+                let Some(&child) = successors.get(next) else {
+                    // All edges of the node are processed
+                    marks.insert(node, Mark::Done);
+                    order.push(node);
+                    stack.pop();
+                    continue;
+                };
+                stack.last_mut().unwrap().1 += 1;
+                match marks.get(&child) {
+                    // Edges to nodes that are not in the graph are skipped
+                    None if self.in_graph(child) => {
+                        marks.insert(child, Mark::Open);
+                        stack.push((child, 0));
+                    }
+                    // Edge back to a node on the path closes a cycle
+                    Some(Mark::Open) => {
+                        let begin = stack.iter().position(|&(other, _)| other == child).unwrap();
+                        return Err(GraphError::Cycle(stack[begin..].iter().map(|&(other, _)| other).collect()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        order.reverse();
+        Ok(order)
+    }
+
+    // Function checks if the graph has no cycles
+    pub fn is_acyclic(&self) -> bool {
+        self.toposort().is_ok()
+    }
+
+    // Function groups nodes into layers (Kahn's algorithm)
+    // Nodes of the first layer have no incoming edges. Nodes of each next layer only have
+    // incoming edges from the previous layers, so nodes of one layer can be processed in parallel
+    // If there is a cycle, it is returned as 'GraphError::Cycle'
+    pub fn toposort_layers(&self) -> Result<Vec<Vec<usize>>, GraphError> {
+        // Number of edges coming into each node from nodes that are not processed yet
+        let mut remaining: HashMap<usize, usize> = self.nodes()
+            .map(|node| (node.index, self.in_degree(node.index).unwrap_or(0)))
+            .collect();
+        let mut layer: Vec<usize> = self.nodes()
+            .map(|node| node.index)
+            .filter(|index| remaining[index] == 0)
+            .collect();
+        let mut layers = Vec::new();
+        let mut processed = 0;
+        while !layer.is_empty() {
+            let mut next_layer = Vec::new();
+            for &node in layer.iter() {
+                for &child in self.successors(node).unwrap_or(&[]) {
+                    if let Some(count) = remaining.get_mut(&child) {
+                        *count -= 1;
+                        if *count == 0 {
+                            next_layer.push(child);
+                        }
+                    }
+                }
+            }
+            processed += layer.len();
+            layers.push(std::mem::replace(&mut layer, next_layer));
+        }
+        // Nodes left unprocessed lie on a cycle or behind one. Depth-first-search finds it
+        if processed < self.len() {
+            return match self.toposort() {
+                Err(err) => Err(err),
+                Ok(_) => unreachable!("Graph Without Cycles Must be Fully Layered"),
+            };
+        }
+        Ok(layers)
+    }
+}
@@ -0,0 +1,359 @@
+use super::graph::{Graph, EdgeType};
+use super::error::GraphError;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ops::Add;
+
+// Module of shortest paths between nodes of a graph
+
+// Trait of edge payloads that can be used as distances
+// Default value is the zero distance
+pub trait Measure: Copy + PartialOrd + Add<Output = Self> + Default {}
+
+impl<K: Copy + PartialOrd + Add<Output = K> + Default> Measure for K {}
+
+// Struct of shortest paths from one node to all nodes reachable from it
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<K> {
+    // Node all paths start with
+    pub source: usize,
+    // Length of the shortest path to each reachable node
+    pub distances: HashMap<usize, K>,
+    // Node that goes right before each reachable node on its shortest path
+    // The source has no predecessor
+    pub predecessors: HashMap<usize, usize>,
+}
+
+impl<K: Copy> ShortestPaths<K> {
+    // Function returns the length of the shortest path to the node (if it is reachable)
+    pub fn distance(&self, to: usize) -> Option<K> {
+        self.distances.get(&to).copied()
+    }
+
+    // Function returns the nodes of the shortest path to the node, from the source to it
+    pub fn path_to(&self, to: usize) -> Option<Vec<usize>> {
+        if !self.distances.contains_key(&to) {
+            return None;
+        }
+        Some(reconstruct_path(&self.predecessors, to))
+    }
+}
+
+// Struct of shortest paths between all pairs of nodes
+// Rows and columns are keyed by indexes of nodes, not by their positions in the arena
+#[derive(Clone, Debug)]
+pub struct DistanceMatrix<K> {
+    // Indexes of nodes in the order of rows (and columns)
+    indexes: Vec<usize>,
+    // Row of each node index
+    positions: HashMap<usize, usize>,
+    // Length of the shortest path from the node of row i to the node of column j is at [i * len + j]
+    distances: Vec<Option<K>>,
+    // Row of the node that goes right after the node of row i on the path to the node of column j
+    next: Vec<Option<usize>>,
+}
+
+impl<K: Copy> DistanceMatrix<K> {
+    // Function returns the indexes of all nodes of the matrix
+    pub fn nodes(&self) -> &[usize] {
+        &self.indexes
+    }
+
+    // Function returns the length of the shortest path between two nodes (if there is a path)
+    pub fn distance(&self, from: usize, to: usize) -> Option<K> {
+        let (i, j) = (*self.positions.get(&from)?, *self.positions.get(&to)?);
+        self.distances[i * self.indexes.len() + j]
+    }
+
+    // Function returns the nodes of the shortest path between two nodes including both ends
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let (i, j) = (*self.positions.get(&from)?, *self.positions.get(&to)?);
+        let len = self.indexes.len();
+        self.distances[i * len + j]?;
+        let mut path = vec![from];
+        let mut current = i;
+        while current != j {
+            current = self.next[current * len + j]?;
+            path.push(self.indexes[current]);
+        }
+        Some(path)
+    }
+}
+
+// Function walks the predecessors back from the node and returns the path leading to it
+pub(crate) fn reconstruct_path(predecessors: &HashMap<usize, usize>, to: usize) -> Vec<usize> {
+    let mut path = vec![to];
+    while let Some(&previous) = predecessors.get(path.last().unwrap()) {
+        path.push(previous);
+    }
+    path.reverse();
+    path
+}
+
+// Entry of the priority queue. Nodes with the smaller distances go first
+pub(crate) struct Scored<K>(pub K, pub usize);
+
+impl<K: PartialOrd> PartialEq for Scored<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: PartialOrd> Eq for Scored<K> {}
+
+impl<K: PartialOrd> PartialOrd for Scored<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: PartialOrd> Ord for Scored<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap, so the order is reversed
+        // Incomparable distances (e.g. NaN) are treated as equal
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T, E, D: EdgeType> Graph<T, E, D> {
+
+    // Function finds the path with the fewest edges from one node to another (Breadth-First Search)
+    // Returns nodes of the path including both ends, or None if there is no path
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if !self.in_graph(from) || !self.in_graph(to) {
+            return None;
+        }
+        let mut predecessors = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        // Nodes are marked as soon as they are added to the queue
+        let mut reached = HashSet::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                return Some(reconstruct_path(&predecessors, to));
+            }
+            for next in self.neighbors(node) {
+                if self.in_graph(next) && reached.insert(next) {
+                    predecessors.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T, E: Measure, D: EdgeType> Graph<T, E, D> {
+
+    // Function finds the shortest paths from the node to all nodes reachable from it (Dijkstra's algorithm)
+    // Payloads of edges are their lengths. A negative one is reported as 'GraphError::NegativeWeight'
+    pub fn dijkstra(&self, from: usize) -> Result<ShortestPaths<E>, GraphError> {
+        if !self.in_graph(from) {
+            return Err(GraphError::MissingNode(from));
+        }
+        let mut distances = HashMap::from([(from, E::default())]);
+        let mut predecessors = HashMap::new();
+        let mut heap = BinaryHeap::from([Scored(E::default(), from)]);
+        while let Some(Scored(distance, node)) = heap.pop() {
+            // Node could be queued several times. Only the shortest distance counts
+            if distances.get(&node).is_some_and(|&best| best < distance) {
+                continue;
+            }
+            for (next, &weight) in self.edges(node).into_iter().flatten() {
+                // Settled nodes would be reopened over and over on a negative cycle
+                if weight < E::default() {
+                    return Err(GraphError::NegativeWeight(node, next));
+                }
+                let candidate = distance + weight;
+                let shorter = match distances.get(&next) {
+                    Some(&best) => candidate < best,
+                    None => self.in_graph(next),
+                };
+                if shorter {
+                    distances.insert(next, candidate);
+                    predecessors.insert(next, node);
+                    heap.push(Scored(candidate, next));
+                }
+            }
+        }
+        Ok(ShortestPaths { source: from, distances, predecessors })
+    }
+
+    // Function finds the shortest paths from the node to all nodes reachable from it (Bellman-Ford algorithm)
+    // Unlike dijkstra() it allows negative lengths of edges
+    // If a cycle of negative total length is reachable, it is returned as 'GraphError::NegativeCycle'
+    pub fn bellman_ford(&self, source: usize) -> Result<ShortestPaths<E>, GraphError> {
+        if !self.in_graph(source) {
+            return Err(GraphError::MissingNode(source));
+        }
+        let mut distances = HashMap::from([(source, E::default())]);
+        let mut predecessors = HashMap::new();
+        // Shortest paths have at most len() - 1 edges, so that many rounds are enough
+        for _ in 1..self.len() {
+            if self.relax_edges(&mut distances, &mut predecessors).is_none() {
+                return Ok(ShortestPaths { source, distances, predecessors });
+            }
+        }
+        // Any edge that can still be relaxed leads from a negative cycle
+        match self.relax_edges(&mut distances, &mut predecessors) {
+            None => Ok(ShortestPaths { source, distances, predecessors }),
+            Some(mut node) => {
+                // Walking back len() steps surely ends up on the cycle
+                for _ in 0..self.len() {
+                    node = predecessors[&node];
+                }
+                let mut cycle = vec![node];
+                let mut previous = predecessors[&node];
+                while previous != node {
+                    cycle.push(previous);
+                    previous = predecessors[&previous];
+                }
+                cycle.reverse();
+                Err(GraphError::NegativeCycle(cycle))
+            }
+        }
+    }
+
+    // Function finds the shortest paths between all pairs of nodes (Floyd-Warshall algorithm)
+    // Takes time cubic in the number of nodes, so it is meant for small and medium graphs
+    // Negative lengths are allowed. A negative cycle is returned as 'GraphError::NegativeCycle'
+    pub fn floyd_warshall(&self) -> Result<DistanceMatrix<E>, GraphError> {
+        let indexes: Vec<usize> = self.nodes().map(|node| node.index).collect();
+        let positions: HashMap<usize, usize> = indexes.iter().enumerate().map(|(i, &index)| (index, i)).collect();
+        let len = indexes.len();
+        let mut distances = vec![None; len * len];
+        let mut next = vec![None; len * len];
+        for i in 0..len {
+            distances[i * len + i] = Some(E::default());
+            next[i * len + i] = Some(i);
+        }
+        // Paths of a single edge. The shortest of parallel edges is taken
+        for (i, node) in self.nodes().enumerate() {
+            for (to, &weight) in self.edges(node.index).into_iter().flatten() {
+                let Some(&j) = positions.get(&to) else {
+                    continue;
+                };
+                if distances[i * len + j].is_none_or(|best| weight < best) {
+                    distances[i * len + j] = Some(weight);
+                    next[i * len + j] = Some(j);
+                }
+            }
+        }
+        // Paths going through the nodes of rows up to k
+        for k in 0..len {
+            for i in 0..len {
+                let Some(to_k) = distances[i * len + k] else {
+                    continue;
+                };
+                for j in 0..len {
+                    let Some(from_k) = distances[k * len + j] else {
+                        continue;
+                    };
+                    let candidate = to_k + from_k;
+                    if distances[i * len + j].is_none_or(|best| candidate < best) {
+                        distances[i * len + j] = Some(candidate);
+                        next[i * len + j] = next[i * len + k];
+                    }
+                }
+            }
+            // Going on after a cycle turns negative lets the distances overflow, so stop right away
+            if let Some(cycle) = negative_cycle(&distances, &next, len) {
+                return Err(GraphError::NegativeCycle(cycle.into_iter().map(|row| indexes[row]).collect()));
+            }
+        }
+        Ok(DistanceMatrix { indexes, positions, distances, next })
+    }
+
+    // Function makes one round of Bellman-Ford over all edges
+    // Returns the last node whose distance got shorter, or None if nothing changed
+    fn relax_edges(&self, distances: &mut HashMap<usize, E>, predecessors: &mut HashMap<usize, usize>) -> Option<usize> {
+        let mut changed = None;
+        for node in self.nodes() {
+            let Some(&distance) = distances.get(&node.index) else {
+                continue;
+            };
+            for (next, &weight) in self.edges(node.index).into_iter().flatten() {
+                let candidate = distance + weight;
+                let shorter = match distances.get(&next) {
+                    Some(&best) => candidate < best,
+                    None => self.in_graph(next),
+                };
+                if shorter {
+                    distances.insert(next, candidate);
+                    predecessors.insert(next, node.index);
+                    changed = Some(next);
+                }
+            }
+        }
+        changed
+    }
+}
+
+impl<T, E, D: EdgeType> Graph<T, E, D> {
+
+    // Function finds the cheapest path from one node to another (A* search)
+    // 'edge_cost' gives the cost of an edge out of its ends and payload. A negative cost is reported
+    // as 'GraphError::NegativeWeight'
+    // 'heuristic' estimates the cost from a node (its index and value) to the goal. It must never
+    // overestimate it, otherwise the path found may be not the cheapest one
+    // Returns nodes of the path including both ends and its total cost, or None if there is no path
+    pub fn astar<K, C, H>(&self, start: usize, goal: usize, mut edge_cost: C, mut heuristic: H) -> Result<Option<(Vec<usize>, K)>, GraphError>
+    where K: Measure, C: FnMut(usize, usize, &E) -> K, H: FnMut(usize, &T) -> K {
+        let estimate = heuristic(start, &self.get_node(start).ok_or(GraphError::MissingNode(start))?.value);
+        if !self.in_graph(goal) {
+            return Err(GraphError::MissingNode(goal));
+        }
+        // Cost of the cheapest path found so far to each node
+        let mut costs = HashMap::from([(start, K::default())]);
+        let mut predecessors = HashMap::new();
+        // Nodes whose edges are already processed with their current costs
+        let mut closed = HashSet::new();
+        // Nodes are ordered by the cost so far plus the estimated cost to the goal
+        let mut heap = BinaryHeap::from([Scored(estimate, start)]);
+        while let Some(Scored(_, node)) = heap.pop() {
+            if node == goal {
+                return Ok(Some((reconstruct_path(&predecessors, goal), costs[&goal])));
+            }
+            if !closed.insert(node) {
+                continue;
+            }
+            let cost = costs[&node];
+            for (next, weight) in self.edges(node).into_iter().flatten() {
+                let Some(other) = self.get_node(next) else {
+                    continue;
+                };
+                // Closed nodes would be reopened over and over on a negative cycle
+                let step = edge_cost(node, next, weight);
+                if step < K::default() {
+                    return Err(GraphError::NegativeWeight(node, next));
+                }
+                let candidate = cost + step;
+                if costs.get(&next).is_none_or(|&best| candidate < best) {
+                    // Heuristic that is not consistent may close a node too early. It is opened again
+                    closed.remove(&next);
+                    costs.insert(next, candidate);
+                    predecessors.insert(next, node);
+                    heap.push(Scored(candidate + heuristic(next, &other.value), next));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+// Function finds a negative cycle in the matrices of Floyd-Warshall, given as rows of its nodes
+// Node with a negative path to itself lies on a negative cycle
+fn negative_cycle<E: Measure>(distances: &[Option<E>], next: &[Option<usize>], len: usize) -> Option<Vec<usize>> {
+    let i = (0..len).find(|&i| distances[i * len + i].is_some_and(|distance| distance < E::default()))?;
+    // Following the path back to the node until some node repeats gives the cycle
+    let mut walk = vec![i];
+    let mut current = i;
+    while let Some(next_hop) = next[current * len + i] {
+        if let Some(begin) = walk.iter().position(|&other| other == next_hop) {
+            walk.drain(..begin);
+            return Some(walk);
+        }
+        walk.push(next_hop);
+        current = next_hop;
+    }
+    Some(vec![i])
+}
@@ -0,0 +1,127 @@
+use super::graph::{Graph, EdgeType};
+use std::collections::HashSet;
+
+// Module of a depth-first-search with a visitor
+// Unlike GraphIter it reports every step of the search, not only discovered nodes
+
+// Event of the depth-first-search passed to the visitor
+// Time is a counter that goes up by one with each discovery and each finish
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DfsEvent {
+    // Node is seen for the first time (node, time)
+    Discover(usize, usize),
+    // Edge leads to a node that is not discovered yet. It is discovered next (from, to)
+    TreeEdge(usize, usize),
+    // Edge leads to a node that is discovered but not finished, which closes a cycle (from, to)
+    BackEdge(usize, usize),
+    // Edge leads to a node that is already finished (from, to)
+    ForwardOrCrossEdge(usize, usize),
+    // All edges of the node are processed (node, time)
+    Finish(usize, usize),
+}
+
+// Answer of the visitor telling how to go on with the search
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    // Go on as usual
+    Continue,
+    // After Discover: skip the edges of the node, it is finished right away
+    // After TreeEdge: don't follow the edge, the node stays undiscovered
+    // Ignored after other events
+    Prune,
+    // Stop the search
+    Break,
+}
+
+impl<T, E, D: EdgeType> Graph<T, E, D> {
+
+    // Function runs a depth-first-search from each of the given nodes that is not discovered yet
+    // and passes its events to the visitor. Nodes that are not in the graph are skipped
+    // To search the whole graph pass all its indexes: graph.nodes().map(|node| node.index)
+    // In undirected graphs each edge is seen from both of its ends. The tree edge a node was
+    // discovered by is not reported again from its other end (a parallel edge still is a BackEdge)
+    // Returns Control::Break if the visitor stopped the search, Control::Continue otherwise
+    pub fn depth_first_search<I, F>(&self, starts: I, mut visitor: F) -> Control
+    where I: IntoIterator<Item = usize>, F: FnMut(DfsEvent) -> Control {
+        let mut time = 0;
+        let mut discovered = HashSet::new();
+        let mut finished = HashSet::new();
+        for start in starts {
+            if !self.in_graph(start) || !discovered.insert(start) {
+                continue;
+            }
+            match discover(&mut visitor, &mut time, start) {
+                Control::Break => return Control::Break,
+                Control::Prune => {
+                    finished.insert(start);
+                    continue;
+                }
+                Control::Continue => {}
+            }
+            // Path of the search with the edges of its nodes that are not processed yet
+            // In undirected graphs each node also keeps its parent until the edge back to it is skipped
+            let mut stack = vec![(start, None, self.neighbors(start))];
+            while let Some((node, parent, edges)) = stack.last_mut() {
+                let node = *node;
+                let Some(child) = edges.next() else {
+                    // All edges of the node are processed
+                    stack.pop();
+                    finished.insert(node);
+                    if visitor(DfsEvent::Finish(node, time)) == Control::Break {
+                        return Control::Break;
+                    }
+                    time += 1;
+                    continue;
+                };
+                // Edges to nodes that are not in the graph are skipped
+                if !self.in_graph(child) {
+                    continue;
+                }
+                if *parent == Some(child) {
+                    *parent = None;
+                    continue;
+                }
+                let event = if !discovered.contains(&child) {
+                    DfsEvent::TreeEdge(node, child)
+                } else if !finished.contains(&child) {
+                    DfsEvent::BackEdge(node, child)
+                } else {
+                    DfsEvent::ForwardOrCrossEdge(node, child)
+                };
+                match visitor(event) {
+                    Control::Break => return Control::Break,
+                    Control::Prune => continue,
+                    Control::Continue => {}
+                }
+                if let DfsEvent::TreeEdge(_, child) = event {
+                    discovered.insert(child);
+                    match discover(&mut visitor, &mut time, child) {
+                        Control::Break => return Control::Break,
+                        Control::Prune => {
+                            finished.insert(child);
+                        }
+                        Control::Continue => {
+                            let parent = if D::is_directed() { None } else { Some(node) };
+                            stack.push((child, parent, self.neighbors(child)));
+                        }
+                    }
+                }
+            }
+        }
+        Control::Continue
+    }
+}
+
+// Function passes the discovery of a node to the visitor
+// A pruned node is finished right away
+fn discover<F: FnMut(DfsEvent) -> Control>(visitor: &mut F, time: &mut usize, node: usize) -> Control {
+    let control = visitor(DfsEvent::Discover(node, *time));
+    *time += 1;
+    if control == Control::Prune {
+        if visitor(DfsEvent::Finish(node, *time)) == Control::Break {
+            return Control::Break;
+        }
+        *time += 1;
+    }
+    control
+}
@@ -21,10 +21,9 @@ fn main() {
     graph.add_node(Node::new(222,"Text", None)).unwrap();
 
 	// Set the root of a graph
-	match graph.set_root(Some(666)){
-		Err(_) => println!("Could not Set a Given Node as a Root!"),
-		Ok(_) => ()
-	};
+	if graph.set_root(Some(666)).is_err() {
+		println!("Could not Set a Given Node as a Root!");
+	}
 
 	// Paths for serializing/deserializing
 	// Two different files on purpose. The second contains lots of labels
@@ -34,14 +33,14 @@ fn main() {
 	// Create a handler that will serialize/deserialize the graph
 	let handler = GraphHandler::new();
 	// Write the graph into file
-	handler.serialize(&mut graph, &into_path).expect("Graph Can Not be Serialized!");
+	handler.serialize(&graph, &into_path).expect("Graph Can Not be Serialized!");
 
 	// WARNING!
 
 	// In order for the deserialization to work correctly it is a MUST
 	// to give a type annotation for the graph that will include the 
 	// deserialized nodes!
-	// Only types implementing 'Default' are available!
+	// Node labels are parsed into values of that type (see 'ValueCodec')
 	let mut fresh_graph: Graph<String> = Graph::new();
 	// Read graph nodes from the other file
 	handler.deserialize(&mut fresh_graph, &from_path).unwrap();